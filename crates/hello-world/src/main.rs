@@ -3,63 +3,15 @@
 
 use std::ffi::{CStr, CString};
 use std::ptr;
+use std::sync::atomic::{AtomicU32, Ordering};
 
 // Everything prefixed with `JPC_` comes from the joltc_sys crate.
 use joltc_sys::*;
 
 use rolt::{
-    BroadPhaseLayer, BroadPhaseLayerInterface, CastShapeArgs, CastShapeCollectorImpl,
-    ClosestHitCastShapeCollector, IntoJolt, ObjectLayer, ObjectLayerPairFilter,
-    ObjectVsBroadPhaseLayerFilter, RShapeCast, RVec3, Vec3,
+    CollisionLayers, ContactListener, ContactListenerImpl, Quat, RVec3, Shape, ShapeCastQuery, Vec3,
 };
 
-const OL_NON_MOVING: JPC_ObjectLayer = 0;
-const OL_MOVING: JPC_ObjectLayer = 1;
-
-const BPL_NON_MOVING: JPC_BroadPhaseLayer = 0;
-const BPL_MOVING: JPC_BroadPhaseLayer = 1;
-const BPL_COUNT: JPC_BroadPhaseLayer = 2;
-
-struct BroadPhaseLayers;
-
-impl BroadPhaseLayerInterface for BroadPhaseLayers {
-    fn get_num_broad_phase_layers(&self) -> u32 {
-        BPL_COUNT as u32
-    }
-
-    fn get_broad_phase_layer(&self, layer: ObjectLayer) -> BroadPhaseLayer {
-        match layer.raw() {
-            OL_NON_MOVING => BroadPhaseLayer::new(BPL_NON_MOVING),
-            OL_MOVING => BroadPhaseLayer::new(BPL_MOVING),
-            _ => unreachable!(),
-        }
-    }
-}
-
-struct ObjectVsBroadPhase;
-
-impl ObjectVsBroadPhaseLayerFilter for ObjectVsBroadPhase {
-    fn should_collide(&self, layer1: ObjectLayer, layer2: BroadPhaseLayer) -> bool {
-        match layer1.raw() {
-            OL_NON_MOVING => layer2.raw() == BPL_MOVING,
-            OL_MOVING => true,
-            _ => unreachable!(),
-        }
-    }
-}
-
-struct ObjectLayerPair;
-
-impl ObjectLayerPairFilter for ObjectLayerPair {
-    fn should_collide(&self, layer1: ObjectLayer, layer2: ObjectLayer) -> bool {
-        match layer1.raw() {
-            OL_NON_MOVING => layer2.raw() == OL_MOVING,
-            OL_MOVING => true,
-            _ => unreachable!(),
-        }
-    }
-}
-
 fn main() {
     rolt::register_default_allocator();
     rolt::factory_init();
@@ -71,29 +23,33 @@ fn main() {
         let job_system =
             JPC_JobSystemThreadPool_new2(JPC_MAX_PHYSICS_JOBS as _, JPC_MAX_PHYSICS_BARRIERS as _);
 
-        let broad_phase_layer_interface = BroadPhaseLayers;
-        let object_vs_broad_phase_layer_filter = ObjectVsBroadPhase;
-        let object_layer_pair_filter = ObjectLayerPair;
+        let layers = CollisionLayers::builder()
+            .broad_phase_layer("non_moving")
+            .broad_phase_layer("moving")
+            .object_layer("non_moving", "non_moving")
+            .object_layer("moving", "moving")
+            .collide("non_moving", "moving")
+            .collide("moving", "moving")
+            .build()
+            .unwrap();
 
-        let mut physics_system = rolt::PhysicsSystem::new();
+        let ol_non_moving = layers.object_layer("non_moving").unwrap().raw();
+        let ol_moving = layers.object_layer("moving").unwrap().raw();
 
-        let max_bodies = 1024;
-        let num_body_mutexes = 0;
-        let max_body_pairs = 1024;
-        let max_contact_constraints = 1024;
+        // The listener is borrowed by the physics system, so it can live on
+        // the stack instead of being shared through an Arc.
+        let mut contacts = ContactCounter::default();
 
-        physics_system.init(
-            max_bodies,
-            num_body_mutexes,
-            max_body_pairs,
-            max_contact_constraints,
-            broad_phase_layer_interface,
-            object_vs_broad_phase_layer_filter,
-            object_layer_pair_filter,
-        );
+        let mut physics_system = rolt::PhysicsSystem::builder()
+            .max_bodies(1024)
+            .max_body_pairs(1024)
+            .max_contact_constraints(1024)
+            .collision_layers(&layers)
+            .contact_listener(ContactListenerImpl::new_borrowed(&mut contacts))
+            .build()
+            .unwrap();
 
         // TODO: register body activation listener
-        // TODO: register contact listener
 
         let body_interface = physics_system.body_interface();
 
@@ -107,7 +63,7 @@ fn main() {
             .create_body(&JPC_BodyCreationSettings {
                 Position: rvec3(0.0, -1.0, 0.0),
                 MotionType: JPC_MOTION_TYPE_STATIC,
-                ObjectLayer: OL_NON_MOVING,
+                ObjectLayer: ol_non_moving,
                 Shape: floor_shape,
                 ..Default::default()
             })
@@ -125,7 +81,7 @@ fn main() {
             .create_body(&JPC_BodyCreationSettings {
                 Position: rvec3(0.0, 2.0, 0.0),
                 MotionType: JPC_MOTION_TYPE_DYNAMIC,
-                ObjectLayer: OL_MOVING,
+                ObjectLayer: ol_moving,
                 Shape: sphere_shape,
                 ..Default::default()
             })
@@ -141,9 +97,12 @@ fn main() {
         let collision_steps = 1;
 
         let mut step = 0;
-        while body_interface.is_active(sphere_id) {
+        while physics_system.body_interface().is_active(sphere_id) {
             step += 1;
 
+            // Updating needs the system to itself, so the body interface is
+            // fetched again each step.
+            let body_interface = physics_system.body_interface();
             let position = body_interface.center_of_mass_position(sphere_id);
             let velocity = body_interface.linear_velocity(sphere_id);
             println!(
@@ -151,30 +110,25 @@ fn main() {
                 position.x, position.y, position.z, velocity.x, velocity.y, velocity.z
             );
 
-            physics_system.update(delta_time, collision_steps, temp_allocator, job_system);
+            physics_system
+                .update(delta_time, collision_steps, temp_allocator, job_system)
+                .unwrap();
         }
 
         // TEMPORARY: test out safe shapecasting API
         let narrow_phase = physics_system.narrow_phase_query();
 
-        let mut collector = ClosestHitCastShapeCollector::new();
-        narrow_phase.cast_shape(CastShapeArgs {
-            shapecast: RShapeCast {
-                shape: sphere_shape,
-                scale: Vec3::ONE,
-                center_of_mass_start: rmat44_translation(RVec3::new(-5.0, 0.0, 0.0).into_jolt()),
-                direction: Vec3::new(10.0, 0.0, 0.0),
-            },
-            base_offset: RVec3::ZERO,
-            settings: Default::default(),
-            collector: Some(CastShapeCollectorImpl::new_borrowed(&mut collector)),
-            broad_phase_layer_filter: None,
-            object_layer_filter: None,
-            body_filter: None,
-            shape_filter: None,
-        });
-
-        println!("Hit: {}", collector.result.is_some());
+        let sphere_shape = Shape::from_raw(sphere_shape);
+        let hit = narrow_phase.cast_shape_closest(ShapeCastQuery::new(
+            &sphere_shape,
+            RVec3::new(-5.0, 0.0, 0.0),
+            Quat::IDENTITY,
+            Vec3::new(10.0, 0.0, 0.0),
+        ));
+
+        println!("Hit: {}", hit.is_some());
+
+        let body_interface = physics_system.body_interface();
 
         body_interface.remove_body(floor_id);
         body_interface.destroy_body(floor_id);
@@ -184,6 +138,8 @@ fn main() {
 
         drop(physics_system);
 
+        println!("Contacts added: {}", contacts.added.load(Ordering::Relaxed));
+
         JPC_JobSystemThreadPool_delete(job_system);
         JPC_TempAllocatorImpl_delete(temp_allocator);
     }
@@ -194,6 +150,44 @@ fn main() {
     println!("Hello, world!");
 }
 
+#[derive(Default)]
+struct ContactCounter {
+    added: AtomicU32,
+}
+
+impl ContactListener for ContactCounter {
+    fn on_contact_validate(
+        &self,
+        _body1: &JPC_Body,
+        _body2: &JPC_Body,
+        _base_offset: JPC_RVec3,
+        _collision_result: &JPC_CollideShapeResult,
+    ) -> JPC_ValidateResult {
+        JPC_VALIDATE_RESULT_ACCEPT_ALL_CONTACTS
+    }
+
+    fn on_contact_added(
+        &self,
+        _body1: &JPC_Body,
+        _body2: &JPC_Body,
+        _manifold: &JPC_ContactManifold,
+        _settings: &mut JPC_ContactSettings,
+    ) {
+        self.added.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn on_contact_persisted(
+        &self,
+        _body1: &JPC_Body,
+        _body2: &JPC_Body,
+        _manifold: &JPC_ContactManifold,
+        _settings: &mut JPC_ContactSettings,
+    ) {
+    }
+
+    fn on_contact_removed(&self, _sub_shape_pair: &JPC_SubShapeIDPair) {}
+}
+
 fn create_box(settings: &JPC_BoxShapeSettings) -> Result<*mut JPC_Shape, CString> {
     let mut shape: *mut JPC_Shape = ptr::null_mut();
     let mut err: *mut JPC_String = ptr::null_mut();
@@ -228,33 +222,6 @@ fn rvec3(x: Real, y: Real, z: Real) -> JPC_RVec3 {
     JPC_RVec3 { x, y, z, _w: z }
 }
 
-fn vec4(x: f32, y: f32, z: f32, w: f32) -> JPC_Vec4 {
-    JPC_Vec4 { x, y, z, w }
-}
-
-// If 'double-precision' is set, there is padding in this struct
-#[allow(clippy::needless_update)]
-fn rmat44_identity() -> JPC_RMat44 {
-    unsafe {
-        JPC_RMat44 {
-            col: [
-                vec4(1.0, 0.0, 0.0, 0.0),
-                vec4(0.0, 1.0, 0.0, 0.0),
-                vec4(0.0, 0.0, 1.0, 0.0),
-            ],
-            col3: rvec3(0.0, 0.0, 0.0),
-            ..std::mem::zeroed()
-        }
-    }
-}
-
-fn rmat44_translation(col3: JPC_RVec3) -> JPC_RMat44 {
-    JPC_RMat44 {
-        col3,
-        ..rmat44_identity()
-    }
-}
-
 #[test]
 fn run_main() {
     main();
@@ -28,6 +28,7 @@ ffi_default! {
     JPC_BodyCreationSettings -> JPC_BodyCreationSettings_default,
     JPC_ShapeCastSettings -> JPC_ShapeCastSettings_default,
     JPC_CollideShapeSettings -> JPC_CollideShapeSettings_default,
+    JPC_PhysicsSettings -> JPC_PhysicsSettings_default,
 
     // All of the ShapeSettings types
     JPC_TriangleShapeSettings -> JPC_TriangleShapeSettings_default,
@@ -36,8 +37,16 @@ ffi_default! {
     JPC_SphereShapeSettings -> JPC_SphereShapeSettings_default,
     JPC_CapsuleShapeSettings -> JPC_CapsuleShapeSettings_default,
     JPC_CylinderShapeSettings -> JPC_CylinderShapeSettings_default,
+    JPC_TaperedCapsuleShapeSettings -> JPC_TaperedCapsuleShapeSettings_default,
+    JPC_TaperedCylinderShapeSettings -> JPC_TaperedCylinderShapeSettings_default,
+    JPC_PlaneShapeSettings -> JPC_PlaneShapeSettings_default,
+    JPC_EmptyShapeSettings -> JPC_EmptyShapeSettings_default,
     JPC_ConvexHullShapeSettings -> JPC_ConvexHullShapeSettings_default,
     JPC_SubShapeSettings -> JPC_SubShapeSettings_default,
     JPC_StaticCompoundShapeSettings -> JPC_StaticCompoundShapeSettings_default,
     JPC_MutableCompoundShapeSettings -> JPC_MutableCompoundShapeSettings_default,
+    JPC_ScaledShapeSettings -> JPC_ScaledShapeSettings_default,
+    JPC_RotatedTranslatedShapeSettings -> JPC_RotatedTranslatedShapeSettings_default,
+    JPC_OffsetCenterOfMassShapeSettings -> JPC_OffsetCenterOfMassShapeSettings_default,
+    JPC_HeightFieldShapeSettings -> JPC_HeightFieldShapeSettings_default,
 }
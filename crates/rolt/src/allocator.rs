@@ -0,0 +1,215 @@
+use std::alloc::{self, Layout};
+use std::ffi::c_void;
+use std::mem;
+use std::ptr;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+use joltc_sys::*;
+
+/// Alignment of blocks from Jolt's `Allocate`, which Jolt expects to match
+/// `malloc`.
+const MIN_ALIGN: usize = 16;
+
+static REGISTERED: AtomicBool = AtomicBool::new(false);
+static LIVE_BYTES: AtomicUsize = AtomicUsize::new(0);
+static PEAK_BYTES: AtomicUsize = AtomicUsize::new(0);
+static LIVE_ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static TOTAL_ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+/// Makes Jolt allocate through Rust's global allocator instead of `malloc`,
+/// so its memory shows up in the same place as the rest of the program's and
+/// can be read back with [`memory_stats`].
+///
+/// # Safety
+///
+/// This must be called before any other Jolt function, and at most once.
+/// Jolt would otherwise free blocks from `malloc` through Rust's allocator, or
+/// the other way around. It must not be combined with
+/// [`register_default_allocator`][crate::register_default_allocator].
+pub unsafe fn register_rust_allocator() {
+    let fns = JPC_AllocatorFns {
+        Allocate: Some(allocate),
+        Reallocate: Some(reallocate),
+        Free: Some(free),
+        AlignedAllocate: Some(aligned_allocate),
+        AlignedFree: Some(free),
+    };
+
+    unsafe {
+        JPC_RegisterCustomAllocator(fns);
+    }
+
+    REGISTERED.store(true, Ordering::Release);
+}
+
+/// Memory that Jolt has allocated through [`register_rust_allocator`]. Sizes
+/// are the ones Jolt asked for, without the small header rolt adds to each
+/// block.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryStats {
+    /// Bytes currently allocated.
+    pub live_bytes: usize,
+
+    /// The most bytes that were allocated at once.
+    pub peak_bytes: usize,
+
+    /// Number of blocks currently allocated.
+    pub live_allocations: usize,
+
+    /// Number of blocks allocated since the allocator was registered,
+    /// including ones that have been freed.
+    pub total_allocations: usize,
+}
+
+/// Returns Jolt's memory usage, or `None` if [`register_rust_allocator`]
+/// hasn't been called.
+///
+/// The counters are updated independently, so they may be slightly out of
+/// sync with each other while other threads are allocating.
+pub fn memory_stats() -> Option<MemoryStats> {
+    if !REGISTERED.load(Ordering::Acquire) {
+        return None;
+    }
+
+    Some(MemoryStats {
+        live_bytes: LIVE_BYTES.load(Ordering::Relaxed),
+        peak_bytes: PEAK_BYTES.load(Ordering::Relaxed),
+        live_allocations: LIVE_ALLOCATIONS.load(Ordering::Relaxed),
+        total_allocations: TOTAL_ALLOCATIONS.load(Ordering::Relaxed),
+    })
+}
+
+/// Stored just before each block, because Jolt's `Free` doesn't pass the size
+/// or alignment that Rust needs to deallocate.
+#[repr(C)]
+struct Header {
+    size: usize,
+    align: usize,
+}
+
+/// Distance from the start of the Rust allocation to the block given to Jolt.
+/// It keeps the block aligned and leaves room for the header.
+fn header_offset(align: usize) -> usize {
+    align.max(mem::size_of::<Header>())
+}
+
+fn layout(size: usize, align: usize) -> Option<Layout> {
+    let total = size.checked_add(header_offset(align))?;
+    Layout::from_size_align(total, align).ok()
+}
+
+unsafe fn alloc_block(size: usize, align: usize) -> *mut c_void {
+    let align = align.max(MIN_ALIGN);
+    let Some(layout) = layout(size, align) else {
+        return ptr::null_mut();
+    };
+
+    // SAFETY: The layout has a non-zero size, because it includes the header.
+    let base = unsafe { alloc::alloc(layout) };
+    if base.is_null() {
+        alloc::handle_alloc_error(layout);
+    }
+
+    // SAFETY: The allocation is `header_offset(align)` bytes larger than the
+    // block, and the header fits in front of the block with its alignment.
+    let block = unsafe {
+        let block = base.add(header_offset(align));
+        block.cast::<Header>().sub(1).write(Header { size, align });
+        block
+    };
+
+    record_alloc(size);
+    block.cast()
+}
+
+unsafe extern "C" fn allocate(size: usize) -> *mut c_void {
+    unsafe { alloc_block(size, MIN_ALIGN) }
+}
+
+unsafe extern "C" fn aligned_allocate(size: usize, align: usize) -> *mut c_void {
+    unsafe { alloc_block(size, align) }
+}
+
+unsafe extern "C" fn reallocate(
+    block: *mut c_void,
+    _old_size: usize,
+    new_size: usize,
+) -> *mut c_void {
+    if block.is_null() {
+        return unsafe { allocate(new_size) };
+    }
+
+    // SAFETY: Jolt only passes blocks from `alloc_block`, which are preceded
+    // by their header.
+    let header = unsafe { block.cast::<Header>().sub(1).read() };
+    let offset = header_offset(header.align);
+    let old_layout = layout(header.size, header.align).unwrap();
+    let Some(new_layout) = layout(new_size, header.align) else {
+        return ptr::null_mut();
+    };
+
+    // SAFETY: The block was allocated with `old_layout`, `offset` bytes after
+    // the start of the allocation.
+    let base = unsafe {
+        alloc::realloc(
+            block.cast::<u8>().sub(offset),
+            old_layout,
+            new_layout.size(),
+        )
+    };
+    if base.is_null() {
+        alloc::handle_alloc_error(new_layout);
+    }
+
+    // SAFETY: The allocation keeps its alignment and the same room for the
+    // header.
+    let block = unsafe {
+        let block = base.add(offset);
+        block.cast::<Header>().sub(1).write(Header {
+            size: new_size,
+            align: header.align,
+        });
+        block
+    };
+
+    record_resize(header.size, new_size);
+    block.cast()
+}
+
+unsafe extern "C" fn free(block: *mut c_void) {
+    if block.is_null() {
+        return;
+    }
+
+    // SAFETY: Jolt only frees blocks from `alloc_block` or `reallocate`,
+    // which are preceded by their header and were allocated with its layout.
+    let header = unsafe { block.cast::<Header>().sub(1).read() };
+    let layout = layout(header.size, header.align).unwrap();
+    unsafe {
+        alloc::dealloc(block.cast::<u8>().sub(header_offset(header.align)), layout);
+    }
+
+    record_free(header.size);
+}
+
+fn record_alloc(size: usize) {
+    let live = LIVE_BYTES.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK_BYTES.fetch_max(live, Ordering::Relaxed);
+    LIVE_ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    TOTAL_ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+}
+
+fn record_resize(old_size: usize, new_size: usize) {
+    if new_size >= old_size {
+        let grown = new_size - old_size;
+        let live = LIVE_BYTES.fetch_add(grown, Ordering::Relaxed) + grown;
+        PEAK_BYTES.fetch_max(live, Ordering::Relaxed);
+    } else {
+        LIVE_BYTES.fetch_sub(old_size - new_size, Ordering::Relaxed);
+    }
+}
+
+fn record_free(size: usize) {
+    LIVE_BYTES.fetch_sub(size, Ordering::Relaxed);
+    LIVE_ALLOCATIONS.fetch_sub(1, Ordering::Relaxed);
+}
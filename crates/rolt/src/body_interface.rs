@@ -3,14 +3,27 @@ use std::marker::PhantomData;
 use glam::Quat;
 use joltc_sys::*;
 
-use crate::{Body, BodyId, IntoJolt, IntoRolt, ObjectLayer, RVec3, Vec3};
+use crate::math::rvec3_to_vec3;
+use crate::panic_guard::checked_call;
+use crate::{
+    AABox, Body, BodyId, BroadPhaseLayerFilterImpl, CollisionGroup, HeightFieldRegion,
+    HeightFieldShape, IntoJolt, IntoRolt, ObjectLayer, ObjectLayerFilterImpl, PhysicsMaterialRef,
+    RVec3, Shape, ShapeError, ShapeSubType, SubShapeId, Vec3,
+};
 
+/// Locks the bodies it touches, so it can be shared between threads.
+///
 /// See also: Jolt's [`BodyInterface`](https://jrouwe.github.io/JoltPhysicsDocs/5.1.0/class_body_interface.html) class.
 pub struct BodyInterface<'physics_system> {
     raw: *mut JPC_BodyInterface,
     _phantom: PhantomData<&'physics_system ()>,
 }
 
+// SAFETY: This is Jolt's locking body interface, which is safe to use from
+// several threads at once.
+unsafe impl Send for BodyInterface<'_> {}
+unsafe impl Sync for BodyInterface<'_> {}
+
 impl<'physics_system> BodyInterface<'physics_system> {
     pub(crate) fn new(raw: *mut JPC_BodyInterface) -> Self {
         Self {
@@ -21,8 +34,10 @@ impl<'physics_system> BodyInterface<'physics_system> {
 
     /// # Safety
     /// `settings` must be initialized and valid, with a valid `Shape` pointer.
+    /// The `GroupFilter` of its collision group must be null or outlive the
+    /// body.
     pub unsafe fn create_body(&self, settings: &JPC_BodyCreationSettings) -> Option<Body> {
-        let raw = JPC_BodyInterface_CreateBody(self.raw, settings);
+        let raw = checked_call(|| JPC_BodyInterface_CreateBody(self.raw, settings));
 
         if raw.is_null() {
             None
@@ -32,17 +47,17 @@ impl<'physics_system> BodyInterface<'physics_system> {
     }
 
     pub fn add_body(&self, body_id: BodyId, activation_mode: JPC_Activation) {
-        unsafe {
+        checked_call(|| unsafe {
             JPC_BodyInterface_AddBody(self.raw, body_id.raw(), activation_mode);
-        }
+        });
     }
 
     pub fn remove_body(&self, body_id: BodyId) {
-        unsafe { JPC_BodyInterface_RemoveBody(self.raw, body_id.raw()) }
+        checked_call(|| unsafe { JPC_BodyInterface_RemoveBody(self.raw, body_id.raw()) });
     }
 
     pub fn destroy_body(&self, body_id: BodyId) {
-        unsafe { JPC_BodyInterface_DestroyBody(self.raw, body_id.raw()) }
+        checked_call(|| unsafe { JPC_BodyInterface_DestroyBody(self.raw, body_id.raw()) });
     }
 
     /// # Safety
@@ -54,13 +69,15 @@ impl<'physics_system> BodyInterface<'physics_system> {
         update_mass_properties: bool,
         activation: JPC_Activation,
     ) {
-        JPC_BodyInterface_SetShape(
-            self.raw,
-            body_id.raw(),
-            shape,
-            update_mass_properties,
-            activation,
-        )
+        checked_call(|| {
+            JPC_BodyInterface_SetShape(
+                self.raw,
+                body_id.raw(),
+                shape,
+                update_mass_properties,
+                activation,
+            );
+        });
     }
 
     pub fn is_active(&self, body_id: BodyId) -> bool {
@@ -89,8 +106,147 @@ impl<'physics_system> BodyInterface<'physics_system> {
         }
     }
 
+    pub fn position(&self, body_id: BodyId) -> RVec3 {
+        unsafe { JPC_BodyInterface_GetPosition(self.raw, body_id.raw()).into_rolt() }
+    }
+
+    pub fn rotation(&self, body_id: BodyId) -> Quat {
+        unsafe { JPC_BodyInterface_GetRotation(self.raw, body_id.raw()).into_rolt() }
+    }
+
+    /// Wakes up all bodies whose bounds overlap `bounds`, which is given in
+    /// world space.
+    pub fn activate_bodies_in_aabox(
+        &self,
+        bounds: AABox,
+        broad_phase_layer_filter: Option<&BroadPhaseLayerFilterImpl<'_>>,
+        object_layer_filter: Option<&ObjectLayerFilterImpl<'_>>,
+    ) {
+        let bounds = bounds.into_jolt();
+
+        checked_call(|| unsafe {
+            JPC_BodyInterface_ActivateBodiesInAABox(
+                self.raw,
+                &bounds,
+                broad_phase_layer_filter.into_jolt(),
+                object_layer_filter.into_jolt(),
+            );
+        });
+    }
+
+    /// Replaces the heights of the samples in `region` of the height field
+    /// used by `body_id`, updates the body's bounds in the broad phase, then
+    /// wakes up the bodies resting on that region so they react to the new
+    /// surface.
+    ///
+    /// See [`HeightFieldShape::set_heights`] for the layout of `heights`.
+    ///
+    /// # Safety
+    ///
+    /// `temp_allocator` must be valid, and the shape must not be used by a
+    /// physics update or query running on another thread.
+    pub unsafe fn set_height_field_heights(
+        &self,
+        body_id: BodyId,
+        region: HeightFieldRegion,
+        heights: &[f32],
+        temp_allocator: *mut JPC_TempAllocatorImpl,
+    ) -> Result<(), ShapeError> {
+        let height_field = self.height_field(body_id)?;
+        let old_com = height_field.as_shape().center_of_mass();
+
+        let mut bounds = height_field.region_bounds(region);
+        height_field.set_heights(region, heights, temp_allocator);
+        bounds.encapsulate(&height_field.region_bounds(region));
+
+        // Same order as Jolt's DeformedHeightFieldShapeTest. Without this the
+        // broad phase keeps the old bounds and misses raised terrain.
+        self.notify_shape_changed(body_id, old_com, false, JPC_ACTIVATION_DONT_ACTIVATE);
+        self.activate_bodies_in_local_bounds(body_id, bounds);
+        Ok(())
+    }
+
+    /// Replaces the material indices of the cells in `region` of the height
+    /// field used by `body_id`, then wakes up the bodies resting on that
+    /// region so they react to the new surface.
+    ///
+    /// See [`HeightFieldShape::set_materials`] for the layout of `indices`.
+    ///
+    /// # Safety
+    ///
+    /// `temp_allocator` must be valid, and the shape must not be used by a
+    /// physics update or query running on another thread.
+    pub unsafe fn set_height_field_materials(
+        &self,
+        body_id: BodyId,
+        region: HeightFieldRegion,
+        indices: &[u8],
+        materials: Option<&[*const JPC_PhysicsMaterial]>,
+        temp_allocator: *mut JPC_TempAllocatorImpl,
+    ) -> Result<(), ShapeError> {
+        let height_field = self.height_field(body_id)?;
+
+        height_field.set_materials(region, indices, materials, temp_allocator)?;
+
+        self.activate_bodies_in_local_bounds(body_id, height_field.region_bounds(region));
+        Ok(())
+    }
+
+    fn height_field(&self, body_id: BodyId) -> Result<HeightFieldShape, ShapeError> {
+        let shape = self.shape(body_id);
+
+        shape
+            .as_height_field()
+            .ok_or_else(|| ShapeError::UnexpectedSubType {
+                expected: ShapeSubType::HeightField,
+                actual: shape.sub_type(),
+            })
+    }
+
+    fn activate_bodies_in_local_bounds(&self, body_id: BodyId, bounds: AABox) {
+        let position = rvec3_to_vec3(self.position(body_id));
+        let bounds = bounds.transformed(self.rotation(body_id), position);
+
+        self.activate_bodies_in_aabox(bounds, None, None);
+    }
+
+    pub fn shape(&self, body_id: BodyId) -> Shape {
+        unsafe { Shape::from_raw(JPC_BodyInterface_GetShape(self.raw, body_id.raw())) }
+    }
+
+    /// Returns the material of the sub-shape of a body, like the one reported
+    /// in a [`RayCastResult`][crate::RayCastResult].
+    pub fn material(
+        &self,
+        body_id: BodyId,
+        sub_shape_id: SubShapeId,
+    ) -> Option<PhysicsMaterialRef> {
+        unsafe {
+            let raw = JPC_BodyInterface_GetMaterial(self.raw, body_id.raw(), sub_shape_id.raw());
+            PhysicsMaterialRef::from_raw(raw)
+        }
+    }
+
     pub fn set_object_layer(&self, body_id: BodyId, object_layer: ObjectLayer) {
-        unsafe { JPC_BodyInterface_SetObjectLayer(self.raw, body_id.raw(), object_layer.raw()) }
+        checked_call(|| unsafe {
+            JPC_BodyInterface_SetObjectLayer(self.raw, body_id.raw(), object_layer.raw());
+        });
+    }
+
+    pub fn collision_group(&self, body_id: BodyId) -> CollisionGroup {
+        unsafe { JPC_BodyInterface_GetCollisionGroup(self.raw, body_id.raw()).into_rolt() }
+    }
+
+    /// Moves a body into a collision group.
+    ///
+    /// # Safety
+    ///
+    /// `group.filter` must be null or point to a group filter that outlives
+    /// the body, or until the body is moved to another group.
+    pub unsafe fn set_collision_group(&self, body_id: BodyId, group: CollisionGroup) {
+        checked_call(|| unsafe {
+            JPC_BodyInterface_SetCollisionGroup(self.raw, body_id.raw(), group.into_jolt());
+        });
     }
 
     pub fn notify_shape_changed(
@@ -100,15 +256,15 @@ impl<'physics_system> BodyInterface<'physics_system> {
         update_mass_properties: bool,
         activation: JPC_Activation,
     ) {
-        unsafe {
+        checked_call(|| unsafe {
             JPC_BodyInterface_NotifyShapeChanged(
                 self.raw,
                 body_id.raw(),
                 old_com.into_jolt(),
                 update_mass_properties,
                 activation,
-            )
-        }
+            );
+        });
     }
 
     pub fn set_position_and_rotation_when_changed(
@@ -118,15 +274,15 @@ impl<'physics_system> BodyInterface<'physics_system> {
         rot: Quat,
         activation: JPC_Activation,
     ) {
-        unsafe {
+        checked_call(|| unsafe {
             JPC_BodyInterface_SetPositionAndRotationWhenChanged(
                 self.raw,
                 body_id.raw(),
                 pos.into_jolt(),
                 rot.into_jolt(),
                 activation,
-            )
-        }
+            );
+        });
     }
 
     pub fn raw(&self) -> *mut JPC_BodyInterface {
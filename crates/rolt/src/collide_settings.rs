@@ -0,0 +1,222 @@
+use joltc_sys::*;
+
+use crate::{FromJolt, IntoJolt, Vec3};
+
+/// How triangles and convex shapes that are hit from behind should be treated.
+///
+/// See also: Jolt's [`EBackFaceMode`](https://jrouwe.github.io/JoltPhysicsDocs/5.1.0/_back_face_mode_8h.html) enum.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum BackFaceMode {
+    /// Back faces are ignored.
+    #[default]
+    IgnoreBackFaces,
+
+    /// Back faces are reported as hits.
+    CollideWithBackFaces,
+}
+
+impl IntoJolt for BackFaceMode {
+    type Jolt = JPC_BackFaceMode;
+
+    fn into_jolt(self) -> Self::Jolt {
+        match self {
+            Self::IgnoreBackFaces => JPC_BACK_FACE_MODE_IGNORE,
+            Self::CollideWithBackFaces => JPC_BACK_FACE_MODE_COLLIDE,
+        }
+    }
+}
+
+impl FromJolt for BackFaceMode {
+    type Jolt = JPC_BackFaceMode;
+
+    fn from_jolt(value: Self::Jolt) -> Self {
+        match value {
+            JPC_BACK_FACE_MODE_COLLIDE => Self::CollideWithBackFaces,
+            _ => Self::IgnoreBackFaces,
+        }
+    }
+}
+
+/// How edges that are shared between triangles of a mesh should be treated.
+///
+/// See also: Jolt's [`EActiveEdgeMode`](https://jrouwe.github.io/JoltPhysicsDocs/5.1.0/_active_edge_mode_8h.html) enum.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ActiveEdgeMode {
+    /// Only collide with edges that are active, which avoids ghost collisions
+    /// when sliding along a mesh.
+    #[default]
+    CollideOnlyWithActive,
+
+    /// Collide with all edges.
+    CollideWithAll,
+}
+
+impl IntoJolt for ActiveEdgeMode {
+    type Jolt = JPC_ActiveEdgeMode;
+
+    fn into_jolt(self) -> Self::Jolt {
+        match self {
+            Self::CollideOnlyWithActive => JPC_ACTIVE_EDGE_MODE_COLLIDE_ONLY_WITH_ACTIVE,
+            Self::CollideWithAll => JPC_ACTIVE_EDGE_MODE_COLLIDE_WITH_ALL,
+        }
+    }
+}
+
+impl FromJolt for ActiveEdgeMode {
+    type Jolt = JPC_ActiveEdgeMode;
+
+    fn from_jolt(value: Self::Jolt) -> Self {
+        match value {
+            JPC_ACTIVE_EDGE_MODE_COLLIDE_WITH_ALL => Self::CollideWithAll,
+            _ => Self::CollideOnlyWithActive,
+        }
+    }
+}
+
+/// Whether the faces of the colliding shapes should be collected.
+///
+/// See also: Jolt's [`ECollectFacesMode`](https://jrouwe.github.io/JoltPhysicsDocs/5.1.0/_collect_faces_mode_8h.html) enum.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum CollectFacesMode {
+    CollectFaces,
+
+    #[default]
+    NoFaces,
+}
+
+impl IntoJolt for CollectFacesMode {
+    type Jolt = JPC_CollectFacesMode;
+
+    fn into_jolt(self) -> Self::Jolt {
+        match self {
+            Self::CollectFaces => JPC_COLLECT_FACES_MODE_COLLECT_FACES,
+            Self::NoFaces => JPC_COLLECT_FACES_MODE_NO_FACES,
+        }
+    }
+}
+
+impl FromJolt for CollectFacesMode {
+    type Jolt = JPC_CollectFacesMode;
+
+    fn from_jolt(value: Self::Jolt) -> Self {
+        match value {
+            JPC_COLLECT_FACES_MODE_COLLECT_FACES => Self::CollectFaces,
+            _ => Self::NoFaces,
+        }
+    }
+}
+
+/// Settings for [`NarrowPhaseQuery::cast_shape_with`][crate::NarrowPhaseQuery::cast_shape_with].
+///
+/// See also: Jolt's [`ShapeCastSettings`](https://jrouwe.github.io/JoltPhysicsDocs/5.1.0/class_shape_cast_settings.html) class.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ShapeCastSettings {
+    pub active_edge_mode: ActiveEdgeMode,
+    pub collect_faces_mode: CollectFacesMode,
+    pub collision_tolerance: f32,
+    pub penetration_tolerance: f32,
+    pub active_edge_movement_direction: Vec3,
+    pub back_face_mode_triangles: BackFaceMode,
+    pub back_face_mode_convex: BackFaceMode,
+    pub use_shrunken_shape_and_convex_radius: bool,
+    pub return_deepest_point: bool,
+}
+
+impl Default for ShapeCastSettings {
+    fn default() -> Self {
+        Self::from_jolt(JPC_ShapeCastSettings::default())
+    }
+}
+
+impl IntoJolt for ShapeCastSettings {
+    type Jolt = JPC_ShapeCastSettings;
+
+    fn into_jolt(self) -> Self::Jolt {
+        JPC_ShapeCastSettings {
+            ActiveEdgeMode: self.active_edge_mode.into_jolt(),
+            CollectFacesMode: self.collect_faces_mode.into_jolt(),
+            CollisionTolerance: self.collision_tolerance,
+            PenetrationTolerance: self.penetration_tolerance,
+            ActiveEdgeMovementDirection: self.active_edge_movement_direction.into_jolt(),
+            BackFaceModeTriangles: self.back_face_mode_triangles.into_jolt(),
+            BackFaceModeConvex: self.back_face_mode_convex.into_jolt(),
+            UseShrunkenShapeAndConvexRadius: self.use_shrunken_shape_and_convex_radius,
+            ReturnDeepestPoint: self.return_deepest_point,
+            ..Default::default()
+        }
+    }
+}
+
+impl FromJolt for ShapeCastSettings {
+    type Jolt = JPC_ShapeCastSettings;
+
+    fn from_jolt(value: Self::Jolt) -> Self {
+        Self {
+            active_edge_mode: ActiveEdgeMode::from_jolt(value.ActiveEdgeMode),
+            collect_faces_mode: CollectFacesMode::from_jolt(value.CollectFacesMode),
+            collision_tolerance: value.CollisionTolerance,
+            penetration_tolerance: value.PenetrationTolerance,
+            active_edge_movement_direction: Vec3::from_jolt(value.ActiveEdgeMovementDirection),
+            back_face_mode_triangles: BackFaceMode::from_jolt(value.BackFaceModeTriangles),
+            back_face_mode_convex: BackFaceMode::from_jolt(value.BackFaceModeConvex),
+            use_shrunken_shape_and_convex_radius: value.UseShrunkenShapeAndConvexRadius,
+            return_deepest_point: value.ReturnDeepestPoint,
+        }
+    }
+}
+
+/// Settings for [`NarrowPhaseQuery::collide_shape_with`][crate::NarrowPhaseQuery::collide_shape_with].
+///
+/// See also: Jolt's [`CollideShapeSettings`](https://jrouwe.github.io/JoltPhysicsDocs/5.1.0/class_collide_shape_settings.html) class.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CollideShapeSettings {
+    pub active_edge_mode: ActiveEdgeMode,
+    pub collect_faces_mode: CollectFacesMode,
+    pub collision_tolerance: f32,
+    pub penetration_tolerance: f32,
+    pub active_edge_movement_direction: Vec3,
+
+    /// Shapes closer than this distance are reported as colliding, even if
+    /// they aren't touching.
+    pub max_separation_distance: f32,
+    pub back_face_mode: BackFaceMode,
+}
+
+impl Default for CollideShapeSettings {
+    fn default() -> Self {
+        Self::from_jolt(JPC_CollideShapeSettings::default())
+    }
+}
+
+impl IntoJolt for CollideShapeSettings {
+    type Jolt = JPC_CollideShapeSettings;
+
+    fn into_jolt(self) -> Self::Jolt {
+        JPC_CollideShapeSettings {
+            ActiveEdgeMode: self.active_edge_mode.into_jolt(),
+            CollectFacesMode: self.collect_faces_mode.into_jolt(),
+            CollisionTolerance: self.collision_tolerance,
+            PenetrationTolerance: self.penetration_tolerance,
+            ActiveEdgeMovementDirection: self.active_edge_movement_direction.into_jolt(),
+            MaxSeparationDistance: self.max_separation_distance,
+            BackFaceMode: self.back_face_mode.into_jolt(),
+            ..Default::default()
+        }
+    }
+}
+
+impl FromJolt for CollideShapeSettings {
+    type Jolt = JPC_CollideShapeSettings;
+
+    fn from_jolt(value: Self::Jolt) -> Self {
+        Self {
+            active_edge_mode: ActiveEdgeMode::from_jolt(value.ActiveEdgeMode),
+            collect_faces_mode: CollectFacesMode::from_jolt(value.CollectFacesMode),
+            collision_tolerance: value.CollisionTolerance,
+            penetration_tolerance: value.PenetrationTolerance,
+            active_edge_movement_direction: Vec3::from_jolt(value.ActiveEdgeMovementDirection),
+            max_separation_distance: value.MaxSeparationDistance,
+            back_face_mode: BackFaceMode::from_jolt(value.BackFaceMode),
+        }
+    }
+}
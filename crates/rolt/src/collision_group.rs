@@ -0,0 +1,211 @@
+use std::ptr;
+
+use joltc_sys::*;
+
+use crate::{FromJolt, GroupFilter, GroupFilterImpl, IntoJolt};
+
+/// Which group a body belongs to, for filtering out collisions between
+/// bodies that are in the same object layer, like the parts of a ragdoll.
+///
+/// Two bodies are tested against the [`GroupFilter`] of the first body that
+/// has one. Bodies without a filter always collide.
+///
+/// See also: Jolt's [`CollisionGroup`](https://jrouwe.github.io/JoltPhysicsDocs/5.1.0/class_collision_group.html) class.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CollisionGroup {
+    /// The filter that decides which groups collide, or null for none. Jolt
+    /// keeps this pointer, so it must outlive every body that uses this
+    /// group.
+    pub filter: *const JPC_GroupFilter,
+    pub group_id: u32,
+    pub sub_group_id: u32,
+}
+
+impl CollisionGroup {
+    /// Group ID used by bodies that aren't in a group.
+    pub const INVALID_GROUP: u32 = u32::MAX;
+
+    /// Sub-group ID used by bodies that aren't in a sub-group.
+    pub const INVALID_SUB_GROUP: u32 = u32::MAX;
+
+    /// A group that isn't filtered, which is what bodies use by default.
+    pub const NONE: Self = Self {
+        filter: ptr::null(),
+        group_id: Self::INVALID_GROUP,
+        sub_group_id: Self::INVALID_SUB_GROUP,
+    };
+
+    /// Creates a group that is filtered by `filter`.
+    ///
+    /// # Safety
+    ///
+    /// The group only keeps a pointer to `filter`, so `filter` must outlive
+    /// every body that is given this group.
+    pub unsafe fn new(filter: &GroupFilterImpl<'_>, group_id: u32, sub_group_id: u32) -> Self {
+        Self {
+            filter: filter.raw().cast_const(),
+            group_id,
+            sub_group_id,
+        }
+    }
+}
+
+impl Default for CollisionGroup {
+    fn default() -> Self {
+        Self::NONE
+    }
+}
+
+impl FromJolt for CollisionGroup {
+    type Jolt = JPC_CollisionGroup;
+
+    fn from_jolt(value: Self::Jolt) -> Self {
+        Self {
+            filter: value.GroupFilter,
+            group_id: value.GroupID,
+            sub_group_id: value.SubGroupID,
+        }
+    }
+}
+
+impl IntoJolt for CollisionGroup {
+    type Jolt = JPC_CollisionGroup;
+
+    fn into_jolt(self) -> Self::Jolt {
+        JPC_CollisionGroup {
+            GroupFilter: self.filter,
+            GroupID: self.group_id,
+            SubGroupID: self.sub_group_id,
+        }
+    }
+}
+
+/// Adds typed collision group access to [`JPC_BodyCreationSettings`].
+///
+/// The group's filter is one of the pointers that must be valid when the
+/// settings are passed to
+/// [`BodyInterface::create_body`][crate::BodyInterface::create_body].
+pub trait BodyCreationSettingsExt {
+    fn collision_group(&self) -> CollisionGroup;
+    fn set_collision_group(&mut self, group: CollisionGroup);
+}
+
+impl BodyCreationSettingsExt for JPC_BodyCreationSettings {
+    fn collision_group(&self) -> CollisionGroup {
+        CollisionGroup::from_jolt(self.CollisionGroup)
+    }
+
+    fn set_collision_group(&mut self, group: CollisionGroup) {
+        self.CollisionGroup = group.into_jolt();
+    }
+}
+
+/// A [`GroupFilter`] that keeps a table of which sub-groups of a group can
+/// collide with each other, like the limbs of a ragdoll. Bodies in different
+/// groups or with different filters always collide, and bodies in the same
+/// sub-group never do.
+///
+/// All pairs of different sub-groups collide until they are disabled.
+///
+/// See also: Jolt's [`GroupFilterTable`](https://jrouwe.github.io/JoltPhysicsDocs/5.1.0/class_group_filter_table.html) class.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GroupFilterTable {
+    num_sub_groups: u32,
+
+    /// One bit for each pair of different sub-groups, set if they collide.
+    bits: Vec<u64>,
+}
+
+impl GroupFilterTable {
+    pub fn new(num_sub_groups: u32) -> Self {
+        let num_pairs = pair_count(num_sub_groups);
+
+        Self {
+            num_sub_groups,
+            bits: vec![u64::MAX; num_pairs.div_ceil(64)],
+        }
+    }
+
+    pub fn num_sub_groups(&self) -> u32 {
+        self.num_sub_groups
+    }
+
+    /// # Panics
+    ///
+    /// Panics if either sub-group is not less than [`Self::num_sub_groups`]
+    /// or if they are the same.
+    pub fn enable_collision(&mut self, sub_group_1: u32, sub_group_2: u32) {
+        let bit = self.bit(sub_group_1, sub_group_2);
+        self.bits[bit / 64] |= 1 << (bit % 64);
+    }
+
+    /// # Panics
+    ///
+    /// Panics if either sub-group is not less than [`Self::num_sub_groups`]
+    /// or if they are the same.
+    pub fn disable_collision(&mut self, sub_group_1: u32, sub_group_2: u32) {
+        let bit = self.bit(sub_group_1, sub_group_2);
+        self.bits[bit / 64] &= !(1 << (bit % 64));
+    }
+
+    /// Returns whether two sub-groups collide. A sub-group never collides
+    /// with itself, and a sub-group outside of the table collides with every
+    /// other sub-group.
+    pub fn is_collision_enabled(&self, sub_group_1: u32, sub_group_2: u32) -> bool {
+        if sub_group_1 == sub_group_2 {
+            return false;
+        }
+
+        if sub_group_1 >= self.num_sub_groups || sub_group_2 >= self.num_sub_groups {
+            return true;
+        }
+
+        let bit = self.bit(sub_group_1, sub_group_2);
+        self.bits[bit / 64] & (1 << (bit % 64)) != 0
+    }
+
+    fn bit(&self, sub_group_1: u32, sub_group_2: u32) -> usize {
+        assert!(
+            sub_group_1 < self.num_sub_groups && sub_group_2 < self.num_sub_groups,
+            "sub-groups {sub_group_1} and {sub_group_2} are out of bounds for {} sub-groups",
+            self.num_sub_groups
+        );
+        assert_ne!(
+            sub_group_1, sub_group_2,
+            "a sub-group can't be filtered against itself"
+        );
+
+        let low = sub_group_1.min(sub_group_2);
+        let high = sub_group_1.max(sub_group_2);
+        pair_count(high) + low as usize
+    }
+}
+
+impl GroupFilter for GroupFilterTable {
+    // Same order of checks as Jolt's GroupFilterTable::CanCollide
+    fn can_collide(&self, group_1: &CollisionGroup, group_2: &CollisionGroup) -> bool {
+        if group_1.group_id == CollisionGroup::INVALID_GROUP {
+            return true;
+        }
+
+        if group_1.group_id != group_2.group_id {
+            return true;
+        }
+
+        if group_1.filter != group_2.filter {
+            return true;
+        }
+
+        if group_1.sub_group_id == group_2.sub_group_id {
+            return false;
+        }
+
+        self.is_collision_enabled(group_1.sub_group_id, group_2.sub_group_id)
+    }
+}
+
+/// Number of pairs of different sub-groups below `num_sub_groups`.
+fn pair_count(num_sub_groups: u32) -> usize {
+    let n = num_sub_groups as usize;
+    n * n.saturating_sub(1) / 2
+}
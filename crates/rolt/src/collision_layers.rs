@@ -0,0 +1,385 @@
+use std::fmt;
+use std::sync::Arc;
+
+use joltc_sys::*;
+
+use crate::{
+    BroadPhaseLayer, BroadPhaseLayerFilter, BroadPhaseLayerFilterImpl, BroadPhaseLayerInterface,
+    ObjectLayer, ObjectLayerFilter, ObjectLayerFilterImpl, ObjectLayerPairFilter,
+    ObjectVsBroadPhaseLayerFilter,
+};
+
+/// A table of named object layers, the broad phase layer each one is stored
+/// in, and which object layers collide with each other.
+///
+/// `CollisionLayers` implements [`BroadPhaseLayerInterface`],
+/// [`ObjectVsBroadPhaseLayerFilter`] and [`ObjectLayerPairFilter`], and can be
+/// passed to a [`PhysicsSystem::builder`][crate::PhysicsSystem::builder] with
+/// [`PhysicsSystemBuilder::collision_layers`][crate::PhysicsSystemBuilder::collision_layers].
+/// Clones share the same table.
+///
+/// Layers are numbered in the order they are declared.
+///
+/// ```no_run
+/// # use rolt::CollisionLayers;
+/// let layers = CollisionLayers::builder()
+///     .broad_phase_layer("non_moving")
+///     .broad_phase_layer("moving")
+///     .object_layer("non_moving", "non_moving")
+///     .object_layer("moving", "moving")
+///     .collide("non_moving", "moving")
+///     .collide("moving", "moving")
+///     .build()
+///     .unwrap();
+///
+/// let moving = layers.object_layer("moving").unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct CollisionLayers {
+    inner: Arc<CollisionLayersInner>,
+}
+
+#[derive(Debug)]
+struct CollisionLayersInner {
+    object_layer_names: Vec<String>,
+    broad_phase_layer_names: Vec<String>,
+
+    /// The broad phase layer of each object layer.
+    broad_phase_layers: Vec<BroadPhaseLayer>,
+
+    /// Whether each pair of object layers collides, indexed by
+    /// `layer1 * num_object_layers + layer2`.
+    object_pairs: Vec<bool>,
+
+    /// Whether each object layer collides with anything in each broad phase
+    /// layer, indexed by `object_layer * num_broad_phase_layers +
+    /// broad_phase_layer`.
+    object_vs_broad_phase: Vec<bool>,
+}
+
+impl CollisionLayers {
+    pub fn builder() -> CollisionLayersBuilder {
+        CollisionLayersBuilder::default()
+    }
+
+    pub fn num_object_layers(&self) -> u32 {
+        self.inner.object_layer_names.len() as u32
+    }
+
+    pub fn num_broad_phase_layers(&self) -> u32 {
+        self.inner.broad_phase_layer_names.len() as u32
+    }
+
+    /// Looks up an object layer by the name it was declared with.
+    pub fn object_layer(&self, name: &str) -> Option<ObjectLayer> {
+        let index = self
+            .inner
+            .object_layer_names
+            .iter()
+            .position(|n| n == name)?;
+        Some(ObjectLayer::new(index as JPC_ObjectLayer))
+    }
+
+    /// Looks up a broad phase layer by the name it was declared with.
+    pub fn broad_phase_layer(&self, name: &str) -> Option<BroadPhaseLayer> {
+        let index = self
+            .inner
+            .broad_phase_layer_names
+            .iter()
+            .position(|n| n == name)?;
+        Some(BroadPhaseLayer::new(index as JPC_BroadPhaseLayer))
+    }
+
+    pub fn object_layer_name(&self, layer: ObjectLayer) -> Option<&str> {
+        self.inner
+            .object_layer_names
+            .get(layer.raw() as usize)
+            .map(String::as_str)
+    }
+
+    pub fn broad_phase_layer_name(&self, layer: BroadPhaseLayer) -> Option<&str> {
+        self.inner
+            .broad_phase_layer_names
+            .get(layer.raw() as usize)
+            .map(String::as_str)
+    }
+
+    /// Returns whether two object layers collide. Layers that weren't
+    /// declared don't collide with anything.
+    pub fn object_layers_collide(&self, layer1: ObjectLayer, layer2: ObjectLayer) -> bool {
+        let count = self.inner.object_layer_names.len();
+        let (layer1, layer2) = (layer1.raw() as usize, layer2.raw() as usize);
+
+        layer1 < count && layer2 < count && self.inner.object_pairs[layer1 * count + layer2]
+    }
+
+    /// Returns whether an object layer collides with any of the object layers
+    /// in a broad phase layer. Layers that weren't declared don't collide with
+    /// anything.
+    pub fn object_collides_with_broad_phase(
+        &self,
+        object_layer: ObjectLayer,
+        broad_phase_layer: BroadPhaseLayer,
+    ) -> bool {
+        let object_count = self.inner.object_layer_names.len();
+        let broad_phase_count = self.inner.broad_phase_layer_names.len();
+        let object_layer = object_layer.raw() as usize;
+        let broad_phase_layer = broad_phase_layer.raw() as usize;
+
+        object_layer < object_count
+            && broad_phase_layer < broad_phase_count
+            && self.inner.object_vs_broad_phase
+                [object_layer * broad_phase_count + broad_phase_layer]
+    }
+
+    /// Creates a filter for queries that should only find the object layers
+    /// that `layer` collides with.
+    pub fn object_layer_filter(&self, layer: ObjectLayer) -> ObjectLayerFilterImpl<'static> {
+        ObjectLayerFilterImpl::new(CollisionLayersObjectFilter {
+            layers: self.clone(),
+            layer,
+        })
+    }
+
+    /// Creates a filter for queries that should only find the broad phase
+    /// layers that `layer` collides with.
+    pub fn broad_phase_layer_filter(
+        &self,
+        layer: ObjectLayer,
+    ) -> BroadPhaseLayerFilterImpl<'static> {
+        BroadPhaseLayerFilterImpl::new(CollisionLayersBroadPhaseFilter {
+            layers: self.clone(),
+            layer,
+        })
+    }
+}
+
+impl BroadPhaseLayerInterface for CollisionLayers {
+    fn get_num_broad_phase_layers(&self) -> u32 {
+        self.num_broad_phase_layers()
+    }
+
+    fn get_broad_phase_layer(&self, layer: ObjectLayer) -> BroadPhaseLayer {
+        match self.inner.broad_phase_layers.get(layer.raw() as usize) {
+            Some(broad_phase_layer) => *broad_phase_layer,
+            None => panic!("object layer {} was not declared", layer.raw()),
+        }
+    }
+}
+
+impl ObjectVsBroadPhaseLayerFilter for CollisionLayers {
+    fn should_collide(&self, layer1: ObjectLayer, layer2: BroadPhaseLayer) -> bool {
+        self.object_collides_with_broad_phase(layer1, layer2)
+    }
+}
+
+impl ObjectLayerPairFilter for CollisionLayers {
+    fn should_collide(&self, layer1: ObjectLayer, layer2: ObjectLayer) -> bool {
+        self.object_layers_collide(layer1, layer2)
+    }
+}
+
+struct CollisionLayersObjectFilter {
+    layers: CollisionLayers,
+    layer: ObjectLayer,
+}
+
+impl ObjectLayerFilter for CollisionLayersObjectFilter {
+    fn should_collide(&self, layer: ObjectLayer) -> bool {
+        self.layers.object_layers_collide(self.layer, layer)
+    }
+}
+
+struct CollisionLayersBroadPhaseFilter {
+    layers: CollisionLayers,
+    layer: ObjectLayer,
+}
+
+impl BroadPhaseLayerFilter for CollisionLayersBroadPhaseFilter {
+    fn should_collide(&self, layer: BroadPhaseLayer) -> bool {
+        self.layers
+            .object_collides_with_broad_phase(self.layer, layer)
+    }
+}
+
+/// Declares the layers of a [`CollisionLayers`].
+#[derive(Debug, Default, Clone)]
+pub struct CollisionLayersBuilder {
+    broad_phase_layers: Vec<String>,
+    object_layers: Vec<(String, String)>,
+    pairs: Vec<(String, String)>,
+}
+
+impl CollisionLayersBuilder {
+    pub fn broad_phase_layer(mut self, name: impl Into<String>) -> Self {
+        self.broad_phase_layers.push(name.into());
+        self
+    }
+
+    /// Declares an object layer that is stored in the broad phase layer named
+    /// `broad_phase_layer`.
+    pub fn object_layer(
+        mut self,
+        name: impl Into<String>,
+        broad_phase_layer: impl Into<String>,
+    ) -> Self {
+        self.object_layers
+            .push((name.into(), broad_phase_layer.into()));
+        self
+    }
+
+    /// Lets two object layers collide with each other. The order of the
+    /// layers doesn't matter, and a layer can be paired with itself.
+    pub fn collide(mut self, layer1: impl Into<String>, layer2: impl Into<String>) -> Self {
+        self.pairs.push((layer1.into(), layer2.into()));
+        self
+    }
+
+    pub fn build(self) -> Result<CollisionLayers, CollisionLayersError> {
+        if self.broad_phase_layers.is_empty() || self.object_layers.is_empty() {
+            return Err(CollisionLayersError::Empty);
+        }
+
+        // The maximum value of each layer type is reserved as an invalid layer.
+        if self.broad_phase_layers.len() > JPC_BroadPhaseLayer::MAX as usize {
+            return Err(CollisionLayersError::TooManyBroadPhaseLayers(
+                self.broad_phase_layers.len(),
+            ));
+        }
+
+        if self.object_layers.len() > JPC_ObjectLayer::MAX as usize {
+            return Err(CollisionLayersError::TooManyObjectLayers(
+                self.object_layers.len(),
+            ));
+        }
+
+        let broad_phase_layer_names = self.broad_phase_layers;
+        check_unique(&broad_phase_layer_names)
+            .map_err(CollisionLayersError::DuplicateBroadPhaseLayer)?;
+
+        let (object_layer_names, object_broad_phase_names): (Vec<String>, Vec<String>) =
+            self.object_layers.into_iter().unzip();
+        check_unique(&object_layer_names).map_err(CollisionLayersError::DuplicateObjectLayer)?;
+
+        let broad_phase_layers = object_layer_names
+            .iter()
+            .zip(&object_broad_phase_names)
+            .map(|(object_layer, broad_phase_layer)| {
+                broad_phase_layer_names
+                    .iter()
+                    .position(|name| name == broad_phase_layer)
+                    .map(|index| BroadPhaseLayer::new(index as JPC_BroadPhaseLayer))
+                    .ok_or_else(|| CollisionLayersError::UnknownBroadPhaseLayer {
+                        object_layer: object_layer.clone(),
+                        broad_phase_layer: broad_phase_layer.clone(),
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let object_count = object_layer_names.len();
+        let broad_phase_count = broad_phase_layer_names.len();
+
+        let find_object_layer = |name: &str| {
+            object_layer_names
+                .iter()
+                .position(|n| n == name)
+                .ok_or_else(|| CollisionLayersError::UnknownObjectLayer(name.to_owned()))
+        };
+
+        let mut object_pairs = vec![false; object_count * object_count];
+        let mut object_vs_broad_phase = vec![false; object_count * broad_phase_count];
+
+        for (layer1, layer2) in &self.pairs {
+            let layer1 = find_object_layer(layer1)?;
+            let layer2 = find_object_layer(layer2)?;
+
+            object_pairs[layer1 * object_count + layer2] = true;
+            object_pairs[layer2 * object_count + layer1] = true;
+
+            let broad_phase1 = broad_phase_layers[layer1].raw() as usize;
+            let broad_phase2 = broad_phase_layers[layer2].raw() as usize;
+            object_vs_broad_phase[layer1 * broad_phase_count + broad_phase2] = true;
+            object_vs_broad_phase[layer2 * broad_phase_count + broad_phase1] = true;
+        }
+
+        Ok(CollisionLayers {
+            inner: Arc::new(CollisionLayersInner {
+                object_layer_names,
+                broad_phase_layer_names,
+                broad_phase_layers,
+                object_pairs,
+                object_vs_broad_phase,
+            }),
+        })
+    }
+}
+
+fn check_unique(names: &[String]) -> Result<(), String> {
+    for (index, name) in names.iter().enumerate() {
+        if names[..index].contains(name) {
+            return Err(name.clone());
+        }
+    }
+
+    Ok(())
+}
+
+/// An error returned by [`CollisionLayersBuilder::build`] when the declared
+/// layers don't fit together.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CollisionLayersError {
+    /// No object layers or no broad phase layers were declared.
+    Empty,
+    DuplicateObjectLayer(String),
+    DuplicateBroadPhaseLayer(String),
+
+    /// An object layer was put in a broad phase layer that wasn't declared.
+    UnknownBroadPhaseLayer {
+        object_layer: String,
+        broad_phase_layer: String,
+    },
+
+    /// A pair of colliding layers names an object layer that wasn't declared.
+    UnknownObjectLayer(String),
+    TooManyObjectLayers(usize),
+    TooManyBroadPhaseLayers(usize),
+}
+
+impl fmt::Display for CollisionLayersError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(
+                f,
+                "at least one object layer and one broad phase layer are needed"
+            ),
+            Self::DuplicateObjectLayer(name) => {
+                write!(f, "object layer {name:?} was declared more than once")
+            }
+            Self::DuplicateBroadPhaseLayer(name) => {
+                write!(f, "broad phase layer {name:?} was declared more than once")
+            }
+            Self::UnknownBroadPhaseLayer {
+                object_layer,
+                broad_phase_layer,
+            } => write!(
+                f,
+                "object layer {object_layer:?} uses undeclared broad phase layer {broad_phase_layer:?}"
+            ),
+            Self::UnknownObjectLayer(name) => {
+                write!(f, "object layer {name:?} was not declared")
+            }
+            Self::TooManyObjectLayers(count) => write!(
+                f,
+                "{count} object layers were declared, but at most {} are supported",
+                JPC_ObjectLayer::MAX
+            ),
+            Self::TooManyBroadPhaseLayers(count) => write!(
+                f,
+                "{count} broad phase layers were declared, but at most {} are supported",
+                JPC_BroadPhaseLayer::MAX
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CollisionLayersError {}
@@ -0,0 +1,326 @@
+use joltc_sys::*;
+
+use crate::shape::create_shape;
+use crate::{BodyId, BodyInterface, IntoJolt, Quat, Ref, Shape, ShapeError, ShapeSubType, Vec3};
+
+/// A child of a compound shape.
+///
+/// See also: Jolt's [`CompoundShapeSettings::SubShapeSettings`](https://jrouwe.github.io/JoltPhysicsDocs/5.1.0/struct_compound_shape_settings_1_1_sub_shape_settings.html) struct.
+#[derive(Clone)]
+pub struct SubShapeSettings {
+    pub shape: Shape,
+    pub position: Vec3,
+    pub rotation: Quat,
+
+    /// User data that can be read back with
+    /// [`Shape::sub_shape_user_data`][crate::Shape::sub_shape_user_data].
+    pub user_data: u32,
+}
+
+impl SubShapeSettings {
+    pub fn new(shape: Shape, position: Vec3, rotation: Quat) -> Self {
+        Self {
+            shape,
+            position,
+            rotation,
+            user_data: 0,
+        }
+    }
+}
+
+impl IntoJolt for &SubShapeSettings {
+    type Jolt = JPC_SubShapeSettings;
+
+    fn into_jolt(self) -> Self::Jolt {
+        JPC_SubShapeSettings {
+            Shape: self.shape.raw(),
+            Position: self.position.into_jolt(),
+            Rotation: self.rotation.into_jolt(),
+            UserData: self.user_data,
+            ..Default::default()
+        }
+    }
+}
+
+/// Settings for a compound shape whose children can't change after it is
+/// created.
+///
+/// See also: Jolt's [`StaticCompoundShapeSettings`](https://jrouwe.github.io/JoltPhysicsDocs/5.1.0/class_static_compound_shape_settings.html) class.
+#[derive(Clone)]
+pub struct StaticCompoundShapeSettings {
+    pub user_data: u64,
+    pub sub_shapes: Vec<SubShapeSettings>,
+}
+
+impl StaticCompoundShapeSettings {
+    pub fn new(sub_shapes: Vec<SubShapeSettings>) -> Self {
+        Self {
+            user_data: 0,
+            sub_shapes,
+        }
+    }
+
+    pub fn create(&self) -> Result<Shape, ShapeError> {
+        let sub_shapes: Vec<JPC_SubShapeSettings> =
+            self.sub_shapes.iter().map(IntoJolt::into_jolt).collect();
+
+        let settings = JPC_StaticCompoundShapeSettings {
+            UserData: self.user_data,
+            SubShapes: sub_shapes.as_ptr(),
+            SubShapesLen: sub_shapes.len(),
+            ..Default::default()
+        };
+
+        create_shape(|shape, err| unsafe {
+            JPC_StaticCompoundShapeSettings_Create(&settings, shape, err)
+        })
+    }
+}
+
+/// Settings for a [`MutableCompoundShape`].
+///
+/// See also: Jolt's [`MutableCompoundShapeSettings`](https://jrouwe.github.io/JoltPhysicsDocs/5.1.0/class_mutable_compound_shape_settings.html) class.
+#[derive(Clone)]
+pub struct MutableCompoundShapeSettings {
+    pub user_data: u64,
+    pub sub_shapes: Vec<SubShapeSettings>,
+}
+
+impl MutableCompoundShapeSettings {
+    pub fn new(sub_shapes: Vec<SubShapeSettings>) -> Self {
+        Self {
+            user_data: 0,
+            sub_shapes,
+        }
+    }
+
+    pub fn create(&self) -> Result<MutableCompoundShape, ShapeError> {
+        let sub_shapes: Vec<JPC_SubShapeSettings> =
+            self.sub_shapes.iter().map(IntoJolt::into_jolt).collect();
+
+        let settings = JPC_MutableCompoundShapeSettings {
+            UserData: self.user_data,
+            SubShapes: sub_shapes.as_ptr(),
+            SubShapesLen: sub_shapes.len(),
+            ..Default::default()
+        };
+
+        let shape = create_shape(|shape, err| unsafe {
+            JPC_MutableCompoundShapeSettings_Create(&settings, shape, err)
+        })?;
+
+        // SAFETY: The shape was just created, so nothing else can be using it.
+        Ok(unsafe { MutableCompoundShape::from_shape(shape) }.unwrap())
+    }
+}
+
+/// A compound shape whose children can be added, removed and moved after it
+/// is created, like a building that loses parts.
+///
+/// After changing a shape that is used by a body, Jolt must be told about it
+/// with [`BodyInterface::notify_shape_changed`].
+/// [`MutableCompoundShape::modify_and_notify`] does this automatically.
+///
+/// Jolt doesn't lock the shape while it is changed, so the methods that change
+/// it are unsafe: the shape must not be used by a physics update or query
+/// running on another thread at the same time.
+///
+/// See also: Jolt's [`MutableCompoundShape`](https://jrouwe.github.io/JoltPhysicsDocs/5.1.0/class_mutable_compound_shape.html) class.
+pub struct MutableCompoundShape {
+    raw: Ref<JPC_MutableCompoundShape>,
+}
+
+impl MutableCompoundShape {
+    /// Returns `None` if `shape` is not a mutable compound shape.
+    ///
+    /// # Safety
+    ///
+    /// Other [`Shape`] handles to the same shape must not be used on another
+    /// thread while the returned handle changes it, and no other
+    /// `MutableCompoundShape` may be created for it while this one is alive.
+    /// Shapes from [`MutableCompoundShapeSettings::create`] already come with
+    /// a handle.
+    pub unsafe fn from_shape(shape: Shape) -> Option<Self> {
+        if shape.sub_type() != ShapeSubType::MutableCompound {
+            return None;
+        }
+
+        let raw =
+            unsafe { Ref::from_active(shape.raw().cast::<JPC_MutableCompoundShape>().cast_mut()) };
+
+        Some(Self { raw })
+    }
+
+    /// Returns a handle to this shape that can be given to bodies.
+    pub fn as_shape(&self) -> Shape {
+        unsafe { Shape::from_raw(self.raw.get().cast::<JPC_Shape>()) }
+    }
+
+    pub fn num_sub_shapes(&self) -> u32 {
+        unsafe { JPC_CompoundShape_GetNumSubShapes(self.raw.get().cast::<JPC_CompoundShape>()) }
+    }
+
+    /// Adds a child shape and returns its index.
+    ///
+    /// # Safety
+    ///
+    /// The shape must not be used by a physics update or query running on
+    /// another thread.
+    pub unsafe fn add_shape(
+        &mut self,
+        position: Vec3,
+        rotation: Quat,
+        shape: &Shape,
+        user_data: u32,
+    ) -> u32 {
+        unsafe {
+            JPC_MutableCompoundShape_AddShape(
+                self.raw.get(),
+                position.into_jolt(),
+                rotation.into_jolt(),
+                shape.raw(),
+                user_data,
+            )
+        }
+    }
+
+    /// Removes the child at `index`. The children after it move down by one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not less than [`Self::num_sub_shapes`].
+    ///
+    /// # Safety
+    ///
+    /// The shape must not be used by a physics update or query running on
+    /// another thread.
+    pub unsafe fn remove_shape(&mut self, index: u32) {
+        self.check_index(index);
+
+        unsafe { JPC_MutableCompoundShape_RemoveShape(self.raw.get(), index) }
+    }
+
+    /// Moves the child at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not less than [`Self::num_sub_shapes`].
+    ///
+    /// # Safety
+    ///
+    /// The shape must not be used by a physics update or query running on
+    /// another thread.
+    pub unsafe fn modify_shape(&mut self, index: u32, position: Vec3, rotation: Quat) {
+        self.check_index(index);
+
+        unsafe {
+            JPC_MutableCompoundShape_ModifyShape(
+                self.raw.get(),
+                index,
+                position.into_jolt(),
+                rotation.into_jolt(),
+            )
+        }
+    }
+
+    /// Moves the children from `start_index` on, one for each element of
+    /// `positions` and `rotations`. This is faster than calling
+    /// [`Self::modify_shape`] for each child.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `positions` and `rotations` have different lengths, or if
+    /// they don't fit in the children from `start_index` on.
+    ///
+    /// # Safety
+    ///
+    /// The shape must not be used by a physics update or query running on
+    /// another thread.
+    pub unsafe fn modify_shapes(
+        &mut self,
+        start_index: u32,
+        positions: &[Vec3],
+        rotations: &[Quat],
+    ) {
+        assert_eq!(
+            positions.len(),
+            rotations.len(),
+            "expected as many positions as rotations"
+        );
+
+        let count = positions.len() as u32;
+        assert!(
+            start_index
+                .checked_add(count)
+                .is_some_and(|end| end <= self.num_sub_shapes()),
+            "{count} sub-shapes from index {start_index} are out of bounds for {} sub-shapes",
+            self.num_sub_shapes()
+        );
+
+        let positions: Vec<JPC_Vec3> = positions.iter().map(|p| p.into_jolt()).collect();
+        let rotations: Vec<JPC_Quat> = rotations.iter().map(|r| r.into_jolt()).collect();
+
+        unsafe {
+            JPC_MutableCompoundShape_ModifyShapes(
+                self.raw.get(),
+                start_index,
+                count,
+                positions.as_ptr(),
+                rotations.as_ptr(),
+                size_of::<JPC_Vec3>() as u32,
+                size_of::<JPC_Quat>() as u32,
+            )
+        }
+    }
+
+    /// Moves the center of mass to the center of the children's volume. The
+    /// center of mass does not move when children are changed otherwise.
+    ///
+    /// # Safety
+    ///
+    /// The shape must not be used by a physics update or query running on
+    /// another thread.
+    pub unsafe fn adjust_center_of_mass(&mut self) {
+        unsafe { JPC_MutableCompoundShape_AdjustCenterOfMass(self.raw.get()) }
+    }
+
+    /// Runs `modify` on this shape, then notifies `body_interface` that the
+    /// shape of `body_id` changed, passing the center of mass from before the
+    /// change so the body stays in place.
+    ///
+    /// If `update_mass_properties` is true, the body's mass and inertia are
+    /// recalculated from the new shape.
+    pub fn modify_and_notify<R>(
+        &mut self,
+        body_interface: &BodyInterface<'_>,
+        body_id: BodyId,
+        update_mass_properties: bool,
+        activation: JPC_Activation,
+        modify: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        let old_center_of_mass = self.as_shape().center_of_mass();
+
+        let result = modify(self);
+        body_interface.notify_shape_changed(
+            body_id,
+            old_center_of_mass,
+            update_mass_properties,
+            activation,
+        );
+
+        result
+    }
+
+    pub fn raw(&self) -> *mut JPC_MutableCompoundShape {
+        self.raw.get()
+    }
+
+    fn check_index(&self, index: u32) {
+        let num_sub_shapes = self.num_sub_shapes();
+
+        assert!(
+            index < num_sub_shapes,
+            "sub-shape {index} is out of bounds for {num_sub_shapes} sub-shapes"
+        );
+    }
+}
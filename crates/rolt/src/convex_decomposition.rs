@@ -0,0 +1,815 @@
+use std::collections::HashMap;
+
+use crate::{
+    ConvexHullShapeSettings, Quat, Shape, ShapeError, StaticCompoundShapeSettings,
+    SubShapeSettings, Vec3,
+};
+
+/// Settings for [`ConvexDecomposition::compute`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConvexDecompositionSettings {
+    /// Maximum number of convex hulls to split the mesh into.
+    pub max_hulls: u32,
+
+    /// Maximum number of vertices of each hull. Hulls with more vertices are
+    /// simplified, which makes them slightly smaller than the part of the
+    /// mesh they cover.
+    pub max_vertices_per_hull: u32,
+
+    /// How much empty space a hull may contain before it is split further, as
+    /// a fraction of the volume of the whole mesh.
+    pub concavity_tolerance: f32,
+
+    /// Number of voxels along the longest side of the mesh. Higher values
+    /// follow the mesh more closely but take longer to compute.
+    pub resolution: u32,
+}
+
+impl Default for ConvexDecompositionSettings {
+    fn default() -> Self {
+        Self {
+            max_hulls: 16,
+            max_vertices_per_hull: 32,
+            concavity_tolerance: 0.01,
+            resolution: 32,
+        }
+    }
+}
+
+/// Splits a concave triangle mesh into convex hulls, so it can be used by
+/// dynamic bodies, which can't use mesh shapes.
+///
+/// Like [V-HACD](https://github.com/kmammou/v-hacd), the mesh is voxelized and
+/// then recursively cut along the axis-aligned plane that leaves the least
+/// empty space in the convex hulls of both halves, until every hull is close
+/// enough to the part it covers or there are `max_hulls` hulls.
+///
+/// Voxels touched by the surface count as solid, so each hull is clamped to
+/// the bounds of the part of the mesh it covers. Otherwise hulls would stick
+/// out of the mesh by up to a voxel on every side.
+///
+/// Closed meshes are treated as solid. Open meshes only have their surface
+/// voxelized.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConvexDecomposition {
+    /// Points of each hull, in the same space as the input mesh.
+    pub hulls: Vec<Vec<Vec3>>,
+}
+
+impl ConvexDecomposition {
+    /// Splits the mesh made of `vertices` and `triangles` into convex hulls.
+    ///
+    /// Triangles with out-of-range indices are skipped. Returns no hulls if
+    /// the mesh is empty or flat.
+    pub fn compute(
+        vertices: &[Vec3],
+        triangles: &[[u32; 3]],
+        settings: &ConvexDecompositionSettings,
+    ) -> Self {
+        let Some(grid) = VoxelGrid::new(vertices, triangles, settings.resolution.max(1)) else {
+            return Self::default();
+        };
+
+        let Some(whole) = Part::new(&grid, [0; 3], grid.dims) else {
+            return Self::default();
+        };
+
+        let tolerance = settings.concavity_tolerance as f64 * whole.volume as f64;
+        let max_hulls = settings.max_hulls.max(1) as usize;
+
+        let mut done = Vec::new();
+        let mut pending = vec![whole];
+
+        while done.len() + pending.len() < max_hulls {
+            let Some(worst) = pending
+                .iter()
+                .enumerate()
+                .max_by(|(_, a), (_, b)| a.concavity.total_cmp(&b.concavity))
+                .map(|(index, _)| index)
+            else {
+                break;
+            };
+
+            let part = pending.swap_remove(worst);
+            if part.concavity <= tolerance {
+                done.push(part);
+                break;
+            }
+
+            match part.split(&grid) {
+                Some((left, right)) => {
+                    pending.push(left);
+                    pending.push(right);
+                }
+                None => done.push(part),
+            }
+        }
+
+        done.append(&mut pending);
+
+        let max_vertices = (settings.max_vertices_per_hull as usize).max(4);
+        let hulls = done
+            .iter()
+            .filter_map(|part| {
+                let hull = Hull::new(&part.hull_points(&grid), max_vertices)?;
+                let points = hull.vertices().map(|p| grid.to_mesh_space(p));
+
+                let Some((min, max)) = part.mesh_bounds(&grid) else {
+                    return Some(points.collect());
+                };
+
+                // Parts made only of voxels outside the surface have no
+                // thickness once clamped.
+                if (max - min).min_element() <= grid.voxel_size * 1e-3 {
+                    return None;
+                }
+
+                let mut clamped: Vec<Vec3> = Vec::new();
+                for point in points.map(|point| point.clamp(min, max)) {
+                    if !clamped.contains(&point) {
+                        clamped.push(point);
+                    }
+                }
+
+                Some(clamped)
+            })
+            .collect();
+
+        Self { hulls }
+    }
+
+    /// Builds the settings for a compound shape with a convex hull shape for
+    /// each hull.
+    pub fn to_static_compound_shape_settings(
+        &self,
+    ) -> Result<StaticCompoundShapeSettings, ShapeError> {
+        let sub_shapes = self
+            .hulls
+            .iter()
+            .map(|hull| {
+                let shape = ConvexHullShapeSettings::new(hull.clone()).create()?;
+                Ok(SubShapeSettings::new(shape, Vec3::ZERO, Quat::IDENTITY))
+            })
+            .collect::<Result<Vec<_>, ShapeError>>()?;
+
+        Ok(StaticCompoundShapeSettings::new(sub_shapes))
+    }
+
+    /// Creates a static compound shape with a convex hull shape for each
+    /// hull, which dynamic bodies can use.
+    ///
+    /// Returns [`ShapeError::Invalid`] if there are no hulls, or if Jolt
+    /// can't build one of them.
+    pub fn create_shape(&self) -> Result<Shape, ShapeError> {
+        if self.hulls.is_empty() {
+            return Err(ShapeError::Invalid(
+                "convex decomposition produced no hulls".to_owned(),
+            ));
+        }
+
+        self.to_static_compound_shape_settings()?.create()
+    }
+}
+
+type IVec3 = [i64; 3];
+
+/// A solid voxelization of a mesh, with an empty border of two voxels so that
+/// the outside stays connected around voxels touched by the mesh's bounds.
+struct VoxelGrid {
+    dims: [usize; 3],
+    origin: Vec3,
+    voxel_size: f32,
+    solid: Vec<bool>,
+
+    /// The triangles of the mesh, in mesh space.
+    triangles: Vec<[Vec3; 3]>,
+}
+
+impl VoxelGrid {
+    fn new(vertices: &[Vec3], triangles: &[[u32; 3]], resolution: u32) -> Option<Self> {
+        let triangles: Vec<[Vec3; 3]> = triangles
+            .iter()
+            .filter_map(|triangle| {
+                let [a, b, c] = triangle.map(|index| vertices.get(index as usize).copied());
+                Some([a?, b?, c?])
+            })
+            .collect();
+
+        let mut min = Vec3::splat(f32::MAX);
+        let mut max = Vec3::splat(f32::MIN);
+        for point in triangles.iter().flatten() {
+            min = min.min(*point);
+            max = max.max(*point);
+        }
+
+        let extent = (max - min).max_element();
+        if triangles.is_empty() || !extent.is_finite() || extent <= 0.0 {
+            return None;
+        }
+
+        let voxel_size = extent / resolution as f32;
+        let origin = min - Vec3::splat(2.0 * voxel_size);
+        let dims = ((max - min) / voxel_size)
+            .ceil()
+            .to_array()
+            .map(|size| (size as usize).max(1) + 4);
+
+        let mut grid = Self {
+            dims,
+            origin,
+            voxel_size,
+            solid: vec![false; dims[0] * dims[1] * dims[2]],
+            triangles: Vec::new(),
+        };
+
+        for triangle in &triangles {
+            grid.voxelize_triangle(triangle.map(|point| (point - origin) / voxel_size));
+        }
+
+        grid.triangles = triangles;
+
+        grid.fill_interior();
+        Some(grid)
+    }
+
+    fn index(&self, [x, y, z]: [usize; 3]) -> usize {
+        (z * self.dims[1] + y) * self.dims[0] + x
+    }
+
+    fn is_solid(&self, voxel: [usize; 3]) -> bool {
+        self.solid[self.index(voxel)]
+    }
+
+    fn to_mesh_space(&self, point: IVec3) -> Vec3 {
+        self.origin + Vec3::new(point[0] as f32, point[1] as f32, point[2] as f32) * self.voxel_size
+    }
+
+    /// Marks every voxel that the triangle, given in voxel units, touches.
+    fn voxelize_triangle(&mut self, triangle: [Vec3; 3]) {
+        let min = triangle[0].min(triangle[1]).min(triangle[2]);
+        let max = triangle[0].max(triangle[1]).max(triangle[2]);
+
+        // Triangles that lie on a voxel boundary touch the voxels on both sides.
+        let lo = (min - 1e-3).floor().to_array().map(|v| v.max(0.0) as usize);
+        let hi = (max + 1e-3).floor().to_array();
+        let hi: [usize; 3] =
+            std::array::from_fn(|axis| (hi[axis].max(0.0) as usize).min(self.dims[axis] - 1));
+
+        for z in lo[2]..=hi[2] {
+            for y in lo[1]..=hi[1] {
+                for x in lo[0]..=hi[0] {
+                    let center = Vec3::new(x as f32, y as f32, z as f32) + 0.5;
+
+                    if triangle_overlaps_box(triangle, center, 0.5 + 1e-4) {
+                        let index = self.index([x, y, z]);
+                        self.solid[index] = true;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Flood fills the outside of the mesh from the border, then marks every
+    /// voxel that wasn't reached as solid.
+    fn fill_interior(&mut self) {
+        let mut outside = vec![false; self.solid.len()];
+        let mut stack = vec![[0, 0, 0]];
+        outside[0] = true;
+
+        while let Some(voxel) = stack.pop() {
+            for axis in 0..3 {
+                for forward in [false, true] {
+                    let mut next = voxel;
+
+                    if forward && next[axis] + 1 < self.dims[axis] {
+                        next[axis] += 1;
+                    } else if !forward && next[axis] > 0 {
+                        next[axis] -= 1;
+                    } else {
+                        continue;
+                    }
+
+                    let index = self.index(next);
+                    if !outside[index] && !self.solid[index] {
+                        outside[index] = true;
+                        stack.push(next);
+                    }
+                }
+            }
+        }
+
+        for (solid, outside) in self.solid.iter_mut().zip(outside) {
+            *solid = !outside;
+        }
+    }
+}
+
+/// The solid voxels inside an axis-aligned box of the grid.
+#[derive(Debug)]
+struct Part {
+    lo: [usize; 3],
+    hi: [usize; 3],
+    volume: i64,
+
+    /// Volume of the convex hull of the part that isn't solid, in voxels.
+    concavity: f64,
+}
+
+impl Part {
+    /// Creates a part from the solid voxels in `lo..hi`, shrunk to fit them.
+    fn new(grid: &VoxelGrid, lo: [usize; 3], hi: [usize; 3]) -> Option<Self> {
+        let mut min = hi;
+        let mut max = lo;
+        let mut volume = 0;
+
+        for z in lo[2]..hi[2] {
+            for y in lo[1]..hi[1] {
+                for x in lo[0]..hi[0] {
+                    if grid.is_solid([x, y, z]) {
+                        volume += 1;
+                        min = [min[0].min(x), min[1].min(y), min[2].min(z)];
+                        max = [max[0].max(x + 1), max[1].max(y + 1), max[2].max(z + 1)];
+                    }
+                }
+            }
+        }
+
+        if volume == 0 {
+            return None;
+        }
+
+        let mut part = Self {
+            lo: min,
+            hi: max,
+            volume,
+            concavity: 0.0,
+        };
+
+        let hull_volume = Hull::new(&part.hull_points(grid), usize::MAX)
+            .map(|hull| hull.volume())
+            .unwrap_or(0.0);
+        part.concavity = (hull_volume - volume as f64).max(0.0);
+
+        Some(part)
+    }
+
+    /// Returns the corners of the first and last solid voxel of every row of
+    /// the part along X. The convex hull of these is the convex hull of the
+    /// whole part.
+    fn hull_points(&self, grid: &VoxelGrid) -> Vec<IVec3> {
+        let mut points = Vec::new();
+
+        for z in self.lo[2]..self.hi[2] {
+            for y in self.lo[1]..self.hi[1] {
+                let mut row = (self.lo[0]..self.hi[0]).filter(|&x| grid.is_solid([x, y, z]));
+
+                let Some(first) = row.next() else {
+                    continue;
+                };
+                let last = row.next_back().unwrap_or(first);
+
+                for x in [first, last + 1] {
+                    for (dy, dz) in [(0, 0), (1, 0), (0, 1), (1, 1)] {
+                        points.push([x as i64, (y + dy) as i64, (z + dz) as i64]);
+                    }
+                }
+            }
+        }
+
+        points
+    }
+
+    /// Returns the bounds of the mesh surface inside the part's box, in mesh
+    /// space, or `None` if no triangle crosses it.
+    fn mesh_bounds(&self, grid: &VoxelGrid) -> Option<(Vec3, Vec3)> {
+        let box_min = grid.to_mesh_space(self.lo.map(|v| v as i64));
+        let box_max = grid.to_mesh_space(self.hi.map(|v| v as i64));
+
+        let mut bounds: Option<(Vec3, Vec3)> = None;
+
+        for triangle in &grid.triangles {
+            let mut polygon = triangle.to_vec();
+
+            for axis in 0..3 {
+                polygon = clip_polygon(&polygon, axis, box_min[axis], true);
+                polygon = clip_polygon(&polygon, axis, box_max[axis], false);
+            }
+
+            for point in polygon {
+                bounds = Some(match bounds {
+                    Some((min, max)) => (min.min(point), max.max(point)),
+                    None => (point, point),
+                });
+            }
+        }
+
+        bounds
+    }
+
+    /// Cuts the part in two along the plane that leaves the least concavity
+    /// in both halves.
+    ///
+    /// A few planes along each axis are tried first, then the planes around
+    /// the best one.
+    fn split(&self, grid: &VoxelGrid) -> Option<(Part, Part)> {
+        const MAX_PLANES_PER_AXIS: usize = 8;
+
+        let mut best: Option<Split> = None;
+        let mut best_step = 1;
+
+        for axis in 0..3 {
+            let length = self.hi[axis] - self.lo[axis];
+            if length < 2 {
+                continue;
+            }
+
+            let step = (length - 1).div_ceil(MAX_PLANES_PER_AXIS);
+
+            for plane in (self.lo[axis] + 1..self.hi[axis]).step_by(step) {
+                if self.try_split(grid, axis, plane, &mut best) {
+                    best_step = step;
+                }
+            }
+        }
+
+        let (axis, plane) = best.as_ref().map(|split| (split.axis, split.plane))?;
+        let first = (plane + 1).saturating_sub(best_step).max(self.lo[axis] + 1);
+        let last = (plane + best_step).min(self.hi[axis]);
+
+        for refined in first..last {
+            if refined != plane {
+                self.try_split(grid, axis, refined, &mut best);
+            }
+        }
+
+        best.map(|split| (split.left, split.right))
+    }
+
+    /// Cuts the part at `plane` along `axis`, and replaces `best` if that
+    /// leaves less concavity. Returns whether `best` was replaced.
+    fn try_split(
+        &self,
+        grid: &VoxelGrid,
+        axis: usize,
+        plane: usize,
+        best: &mut Option<Split>,
+    ) -> bool {
+        let mut left_hi = self.hi;
+        left_hi[axis] = plane;
+        let mut right_lo = self.lo;
+        right_lo[axis] = plane;
+
+        let (Some(left), Some(right)) = (
+            Part::new(grid, self.lo, left_hi),
+            Part::new(grid, right_lo, self.hi),
+        ) else {
+            return false;
+        };
+
+        let cost = left.concavity + right.concavity;
+        if best.as_ref().is_some_and(|best| best.cost <= cost) {
+            return false;
+        }
+
+        *best = Some(Split {
+            cost,
+            axis,
+            plane,
+            left,
+            right,
+        });
+
+        true
+    }
+}
+
+struct Split {
+    cost: f64,
+    axis: usize,
+    plane: usize,
+    left: Part,
+    right: Part,
+}
+
+/// A convex hull of points on an integer grid, built with an exact version of
+/// the quickhull algorithm.
+struct Hull {
+    points: Vec<IVec3>,
+    faces: Vec<HullFace>,
+}
+
+struct HullFace {
+    vertices: [usize; 3],
+    normal: IVec3,
+    outside: Vec<usize>,
+    alive: bool,
+}
+
+impl HullFace {
+    fn new(points: &[IVec3], vertices: [usize; 3]) -> Self {
+        let [a, b, c] = vertices.map(|v| points[v]);
+
+        Self {
+            vertices,
+            normal: cross(sub(b, a), sub(c, a)),
+            outside: Vec::new(),
+            alive: true,
+        }
+    }
+
+    /// Signed distance of `point` above the face, scaled by the length of the
+    /// face normal.
+    fn height(&self, points: &[IVec3], point: IVec3) -> i64 {
+        dot(self.normal, sub(point, points[self.vertices[0]]))
+    }
+
+    fn edges(&self) -> [(usize, usize); 3] {
+        let [a, b, c] = self.vertices;
+        [(a, b), (b, c), (c, a)]
+    }
+}
+
+impl Hull {
+    /// Builds the hull of `points`, stopping once it has `max_vertices`
+    /// vertices. Returns `None` if the points are all on one plane.
+    fn new(points: &[IVec3], max_vertices: usize) -> Option<Self> {
+        let mut points = points.to_vec();
+        points.sort_unstable();
+        points.dedup();
+
+        // Points are sorted, so breaking ties by the lowest index picks the
+        // lexicographically smallest point, which is always a corner of the
+        // hull even when many points are on the same plane.
+        let p0 = 0;
+        let p1 = max_index(&points, |p| length_squared(sub(p, points[p0])))?;
+        let p2 = max_index(&points, |p| {
+            length_squared(cross(sub(points[p1], points[p0]), sub(p, points[p0])))
+        })?;
+        let base = cross(sub(points[p1], points[p0]), sub(points[p2], points[p0]));
+        if base == [0; 3] {
+            return None;
+        }
+
+        let p3 = max_index(&points, |p| dot(base, sub(p, points[p0])).abs())?;
+        if dot(base, sub(points[p3], points[p0])) == 0 {
+            return None;
+        }
+
+        let mut hull = Self {
+            points,
+            faces: Vec::new(),
+        };
+        let mut edges = HashMap::new();
+
+        for (vertices, opposite) in [
+            ([p0, p1, p2], p3),
+            ([p0, p3, p1], p2),
+            ([p1, p3, p2], p0),
+            ([p2, p3, p0], p1),
+        ] {
+            let mut face = HullFace::new(&hull.points, vertices);
+            if face.height(&hull.points, hull.points[opposite]) > 0 {
+                face = HullFace::new(&hull.points, [vertices[0], vertices[2], vertices[1]]);
+            }
+
+            hull.add_face(face, &mut edges);
+        }
+
+        let initial = [p0, p1, p2, p3];
+        let remaining: Vec<usize> = (0..hull.points.len())
+            .filter(|i| !initial.contains(i))
+            .collect();
+        hull.assign_outside(remaining, 0);
+
+        let mut vertex_count = 4;
+        while vertex_count < max_vertices {
+            let Some((face, eye)) = hull.farthest_outside() else {
+                break;
+            };
+
+            hull.add_point(face, eye, &mut edges);
+            vertex_count += 1;
+        }
+
+        Some(hull)
+    }
+
+    fn add_face(&mut self, face: HullFace, edges: &mut HashMap<(usize, usize), usize>) {
+        for edge in face.edges() {
+            edges.insert(edge, self.faces.len());
+        }
+
+        self.faces.push(face);
+    }
+
+    /// Gives each point to the first face from `first_face` on that it is
+    /// above. Points that aren't above any face are inside the hull.
+    fn assign_outside(&mut self, points: Vec<usize>, first_face: usize) {
+        for point in points {
+            let position = self.points[point];
+
+            if let Some(face) = self.faces[first_face..]
+                .iter_mut()
+                .find(|face| face.alive && face.height(&self.points, position) > 0)
+            {
+                face.outside.push(point);
+            }
+        }
+    }
+
+    fn farthest_outside(&self) -> Option<(usize, usize)> {
+        let mut best = None;
+        let mut best_distance = 0.0;
+
+        for (index, face) in self.faces.iter().enumerate() {
+            if !face.alive {
+                continue;
+            }
+
+            let normal_length = (length_squared(face.normal) as f64).sqrt();
+
+            for &point in &face.outside {
+                let distance = face.height(&self.points, self.points[point]) as f64 / normal_length;
+
+                let is_better = match best {
+                    Some((_, best_point)) => {
+                        distance > best_distance
+                            || (distance == best_distance && point < best_point)
+                    }
+                    None => distance > best_distance,
+                };
+
+                if is_better {
+                    best_distance = distance;
+                    best = Some((index, point));
+                }
+            }
+        }
+
+        best
+    }
+
+    fn add_point(&mut self, start: usize, eye: usize, edges: &mut HashMap<(usize, usize), usize>) {
+        let eye_position = self.points[eye];
+
+        let mut visible = vec![start];
+        let mut horizon = Vec::new();
+        let mut stack = vec![start];
+        self.faces[start].alive = false;
+
+        while let Some(face) = stack.pop() {
+            for (a, b) in self.faces[face].edges() {
+                let neighbor = edges[&(b, a)];
+
+                if !self.faces[neighbor].alive {
+                    continue;
+                }
+
+                if self.faces[neighbor].height(&self.points, eye_position) > 0 {
+                    self.faces[neighbor].alive = false;
+                    visible.push(neighbor);
+                    stack.push(neighbor);
+                } else {
+                    horizon.push((a, b));
+                }
+            }
+        }
+
+        let mut orphans = Vec::new();
+        for &face in &visible {
+            for edge in self.faces[face].edges() {
+                edges.remove(&edge);
+            }
+
+            orphans.extend(
+                std::mem::take(&mut self.faces[face].outside)
+                    .into_iter()
+                    .filter(|&point| point != eye),
+            );
+        }
+
+        let first_new_face = self.faces.len();
+        for (a, b) in horizon {
+            let face = HullFace::new(&self.points, [a, b, eye]);
+            self.add_face(face, edges);
+        }
+
+        self.assign_outside(orphans, first_new_face);
+    }
+
+    fn vertices(&self) -> impl Iterator<Item = IVec3> + '_ {
+        let mut used = vec![false; self.points.len()];
+
+        for face in self.faces.iter().filter(|face| face.alive) {
+            for &vertex in &face.vertices {
+                used[vertex] = true;
+            }
+        }
+
+        self.points
+            .iter()
+            .zip(used)
+            .filter(|(_, used)| *used)
+            .map(|(point, _)| *point)
+    }
+
+    fn volume(&self) -> f64 {
+        let six_volume: i64 = self
+            .faces
+            .iter()
+            .filter(|face| face.alive)
+            .map(|face| {
+                let [a, b, c] = face.vertices.map(|v| self.points[v]);
+                dot(a, cross(b, c))
+            })
+            .sum();
+
+        six_volume as f64 / 6.0
+    }
+}
+
+/// Returns the index of the point with the highest key, preferring the lowest
+/// index on ties.
+fn max_index(points: &[IVec3], key: impl Fn(IVec3) -> i64) -> Option<usize> {
+    (0..points.len()).max_by_key(|&i| (key(points[i]), std::cmp::Reverse(i)))
+}
+
+fn sub(a: IVec3, b: IVec3) -> IVec3 {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn dot(a: IVec3, b: IVec3) -> i64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: IVec3, b: IVec3) -> IVec3 {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn length_squared(a: IVec3) -> i64 {
+    dot(a, a)
+}
+
+/// Keeps the part of a convex polygon where the coordinate along `axis` is at
+/// least `value`, or at most `value` if `keep_above` is false.
+fn clip_polygon(polygon: &[Vec3], axis: usize, value: f32, keep_above: bool) -> Vec<Vec3> {
+    let inside = |point: Vec3| {
+        if keep_above {
+            point[axis] >= value
+        } else {
+            point[axis] <= value
+        }
+    };
+
+    let mut clipped = Vec::new();
+
+    for (index, &current) in polygon.iter().enumerate() {
+        let previous = polygon[(index + polygon.len() - 1) % polygon.len()];
+
+        if inside(current) != inside(previous) {
+            let t = (value - previous[axis]) / (current[axis] - previous[axis]);
+            let mut crossing = previous.lerp(current, t);
+            crossing[axis] = value;
+            clipped.push(crossing);
+        }
+
+        if inside(current) {
+            clipped.push(current);
+        }
+    }
+
+    clipped
+}
+
+/// Separating axis test between a triangle and an axis-aligned cube.
+fn triangle_overlaps_box(triangle: [Vec3; 3], center: Vec3, half_extent: f32) -> bool {
+    let [v0, v1, v2] = triangle.map(|v| v - center);
+    let edges = [v1 - v0, v2 - v1, v0 - v2];
+
+    let separated = |axis: Vec3| {
+        let (p0, p1, p2) = (v0.dot(axis), v1.dot(axis), v2.dot(axis));
+        let radius = half_extent * axis.abs().element_sum();
+
+        p0.min(p1).min(p2) > radius || p0.max(p1).max(p2) < -radius
+    };
+
+    for edge in edges {
+        for axis in [Vec3::X, Vec3::Y, Vec3::Z] {
+            if separated(edge.cross(axis)) {
+                return false;
+            }
+        }
+    }
+
+    for axis in [Vec3::X, Vec3::Y, Vec3::Z] {
+        if separated(axis) {
+            return false;
+        }
+    }
+
+    !separated(edges[0].cross(edges[1]))
+}
@@ -0,0 +1,64 @@
+use std::ptr;
+
+use joltc_sys::*;
+
+use crate::shape::create_shape;
+use crate::{IntoJolt, PhysicsMaterialRef, Shape, ShapeError, Vec3};
+
+/// Settings for the smallest convex shape that contains a set of points.
+///
+/// See also: Jolt's [`ConvexHullShapeSettings`](https://jrouwe.github.io/JoltPhysicsDocs/5.1.0/class_convex_hull_shape_settings.html) class.
+#[derive(Debug, Clone)]
+pub struct ConvexHullShapeSettings {
+    pub user_data: u64,
+
+    /// Material of the whole shape, or `None` for Jolt's default material.
+    pub material: Option<PhysicsMaterialRef>,
+    pub density: f32,
+    pub points: Vec<Vec3>,
+    pub max_convex_radius: f32,
+    pub max_error_convex_radius: f32,
+
+    /// Points closer than this to the hull are left out of it, which keeps
+    /// the number of faces down.
+    pub hull_tolerance: f32,
+}
+
+impl ConvexHullShapeSettings {
+    pub fn new(points: Vec<Vec3>) -> Self {
+        let defaults = JPC_ConvexHullShapeSettings::default();
+
+        Self {
+            user_data: defaults.UserData,
+            material: None,
+            density: defaults.Density,
+            points,
+            max_convex_radius: defaults.MaxConvexRadius,
+            max_error_convex_radius: defaults.MaxErrorConvexRadius,
+            hull_tolerance: defaults.HullTolerance,
+        }
+    }
+
+    pub fn create(&self) -> Result<Shape, ShapeError> {
+        let points: Vec<JPC_Vec3> = self.points.iter().map(|point| point.into_jolt()).collect();
+
+        let settings = JPC_ConvexHullShapeSettings {
+            UserData: self.user_data,
+            Material: self
+                .material
+                .as_ref()
+                .map_or(ptr::null(), PhysicsMaterialRef::raw),
+            Density: self.density,
+            Points: points.as_ptr(),
+            PointsLen: points.len(),
+            MaxConvexRadius: self.max_convex_radius,
+            MaxErrorConvexRadius: self.max_error_convex_radius,
+            HullTolerance: self.hull_tolerance,
+            ..Default::default()
+        };
+
+        create_shape(|shape, err| unsafe {
+            JPC_ConvexHullShapeSettings_Create(&settings, shape, err)
+        })
+    }
+}
@@ -0,0 +1,115 @@
+use joltc_sys::*;
+
+use crate::shape::create_shape;
+use crate::{IntoJolt, Quat, Shape, ShapeError, Vec3};
+
+/// Settings for a shape that scales another shape.
+///
+/// See also: Jolt's [`ScaledShapeSettings`](https://jrouwe.github.io/JoltPhysicsDocs/5.1.0/class_scaled_shape_settings.html) class.
+#[derive(Clone)]
+pub struct ScaledShapeSettings {
+    pub user_data: u64,
+    pub inner_shape: Shape,
+    pub scale: Vec3,
+}
+
+impl ScaledShapeSettings {
+    pub fn new(inner_shape: Shape, scale: Vec3) -> Self {
+        Self {
+            user_data: 0,
+            inner_shape,
+            scale,
+        }
+    }
+
+    /// Creates the shape, or returns [`ShapeError::UnsupportedScale`] if the
+    /// inner shape can't be scaled by `scale`.
+    pub fn create(&self) -> Result<Shape, ShapeError> {
+        if !self.inner_shape.is_valid_scale(self.scale) {
+            return Err(ShapeError::UnsupportedScale {
+                scale: self.scale,
+                sub_type: self.inner_shape.sub_type(),
+            });
+        }
+
+        let settings = JPC_ScaledShapeSettings {
+            UserData: self.user_data,
+            InnerShape: self.inner_shape.raw(),
+            Scale: self.scale.into_jolt(),
+            ..Default::default()
+        };
+
+        create_shape(|shape, err| unsafe { JPC_ScaledShapeSettings_Create(&settings, shape, err) })
+    }
+}
+
+/// Settings for a shape that moves and rotates another shape relative to its
+/// origin.
+///
+/// See also: Jolt's [`RotatedTranslatedShapeSettings`](https://jrouwe.github.io/JoltPhysicsDocs/5.1.0/class_rotated_translated_shape_settings.html) class.
+#[derive(Clone)]
+pub struct RotatedTranslatedShapeSettings {
+    pub user_data: u64,
+    pub inner_shape: Shape,
+    pub position: Vec3,
+    pub rotation: Quat,
+}
+
+impl RotatedTranslatedShapeSettings {
+    pub fn new(inner_shape: Shape, position: Vec3, rotation: Quat) -> Self {
+        Self {
+            user_data: 0,
+            inner_shape,
+            position,
+            rotation,
+        }
+    }
+
+    pub fn create(&self) -> Result<Shape, ShapeError> {
+        let settings = JPC_RotatedTranslatedShapeSettings {
+            UserData: self.user_data,
+            InnerShape: self.inner_shape.raw(),
+            Position: self.position.into_jolt(),
+            Rotation: self.rotation.into_jolt(),
+            ..Default::default()
+        };
+
+        create_shape(|shape, err| unsafe {
+            JPC_RotatedTranslatedShapeSettings_Create(&settings, shape, err)
+        })
+    }
+}
+
+/// Settings for a shape that moves the center of mass of another shape
+/// without moving the shape itself, like lowering the center of mass of a car.
+///
+/// See also: Jolt's [`OffsetCenterOfMassShapeSettings`](https://jrouwe.github.io/JoltPhysicsDocs/5.1.0/class_offset_center_of_mass_shape_settings.html) class.
+#[derive(Clone)]
+pub struct OffsetCenterOfMassShapeSettings {
+    pub user_data: u64,
+    pub inner_shape: Shape,
+    pub offset: Vec3,
+}
+
+impl OffsetCenterOfMassShapeSettings {
+    pub fn new(inner_shape: Shape, offset: Vec3) -> Self {
+        Self {
+            user_data: 0,
+            inner_shape,
+            offset,
+        }
+    }
+
+    pub fn create(&self) -> Result<Shape, ShapeError> {
+        let settings = JPC_OffsetCenterOfMassShapeSettings {
+            UserData: self.user_data,
+            InnerShape: self.inner_shape.raw(),
+            Offset: self.offset.into_jolt(),
+            ..Default::default()
+        };
+
+        create_shape(|shape, err| unsafe {
+            JPC_OffsetCenterOfMassShapeSettings_Create(&settings, shape, err)
+        })
+    }
+}
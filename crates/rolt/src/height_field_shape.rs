@@ -0,0 +1,403 @@
+use joltc_sys::*;
+
+use crate::shape::create_shape;
+use crate::{AABox, IntoJolt, IntoRolt, Shape, ShapeError, SubShapeId, Vec3};
+
+/// Settings for a shape that represents terrain as a square grid of height
+/// samples.
+///
+/// Sample `(x, y)` is stored at `samples[y * sample_count + x]` and is placed
+/// at `offset + scale * (x, samples[...], y)` in the shape's local space.
+///
+/// See also: Jolt's [`HeightFieldShapeSettings`](https://jrouwe.github.io/JoltPhysicsDocs/5.1.0/class_height_field_shape_settings.html) class.
+#[derive(Clone)]
+pub struct HeightFieldShapeSettings<'a> {
+    pub user_data: u64,
+
+    /// Heights of the samples, or [`HeightFieldShape::NO_COLLISION_VALUE`] to
+    /// leave a hole in the terrain.
+    pub samples: &'a [f32],
+
+    /// Number of samples along each side of the grid.
+    pub sample_count: u32,
+    pub offset: Vec3,
+    pub scale: Vec3,
+
+    /// Size of the blocks that samples are compressed in. Must be between 2
+    /// and 8, and `sample_count` must be a multiple of it.
+    pub block_size: u32,
+
+    /// Number of bits used to store each sample, between 1 and 8. Fewer bits
+    /// use less memory but make the terrain less accurate.
+    pub bits_per_sample: u32,
+
+    /// Index into `materials` for each cell of the grid, of which there are
+    /// `(sample_count - 1)²`. Can be left empty to use the first material for
+    /// every cell.
+    pub material_indices: &'a [u8],
+
+    /// Materials of the terrain, like the ones from
+    /// [`PhysicsMaterialList::raw_materials`][crate::PhysicsMaterialList::raw_materials].
+    pub materials: &'a [*const JPC_PhysicsMaterial],
+}
+
+impl<'a> HeightFieldShapeSettings<'a> {
+    pub fn new(samples: &'a [f32], sample_count: u32) -> Self {
+        let defaults = JPC_HeightFieldShapeSettings::default();
+
+        Self {
+            user_data: 0,
+            samples,
+            sample_count,
+            offset: defaults.Offset.into_rolt(),
+            scale: defaults.Scale.into_rolt(),
+            block_size: defaults.BlockSize,
+            bits_per_sample: defaults.BitsPerSample,
+            material_indices: &[],
+            materials: &[],
+        }
+    }
+
+    pub fn create(&self) -> Result<Shape, ShapeError> {
+        let expected_samples = self.sample_count as usize * self.sample_count as usize;
+        if self.samples.len() != expected_samples {
+            return Err(ShapeError::Invalid(format!(
+                "expected {expected_samples} height samples for a sample count of {}, got {}",
+                self.sample_count,
+                self.samples.len()
+            )));
+        }
+
+        if !self.material_indices.is_empty() {
+            let cells = self.sample_count.saturating_sub(1) as usize;
+            let expected_indices = cells * cells;
+
+            if self.material_indices.len() != expected_indices {
+                return Err(ShapeError::Invalid(format!(
+                    "expected {expected_indices} material indices, got {}",
+                    self.material_indices.len()
+                )));
+            }
+        }
+
+        let settings = JPC_HeightFieldShapeSettings {
+            UserData: self.user_data,
+            HeightSamples: self.samples.as_ptr(),
+            HeightSamplesLen: self.samples.len(),
+            SampleCount: self.sample_count,
+            Offset: self.offset.into_jolt(),
+            Scale: self.scale.into_jolt(),
+            BlockSize: self.block_size,
+            BitsPerSample: self.bits_per_sample,
+            MaterialIndices: self.material_indices.as_ptr(),
+            MaterialIndicesLen: self.material_indices.len(),
+            Materials: self.materials.as_ptr(),
+            MaterialsLen: self.materials.len(),
+            ..Default::default()
+        };
+
+        create_shape(|shape, err| unsafe {
+            JPC_HeightFieldShapeSettings_Create(&settings, shape, err)
+        })
+    }
+}
+
+/// A view of a [`Shape`] that is known to be a height field.
+///
+/// See also: Jolt's [`HeightFieldShape`](https://jrouwe.github.io/JoltPhysicsDocs/5.1.0/class_height_field_shape.html) class.
+#[derive(Clone)]
+pub struct HeightFieldShape {
+    pub(crate) shape: Shape,
+}
+
+impl HeightFieldShape {
+    /// Sample value that marks a hole in the terrain.
+    pub const NO_COLLISION_VALUE: f32 = f32::MAX;
+
+    pub fn sample_count(&self) -> u32 {
+        unsafe { JPC_HeightFieldShape_GetSampleCount(self.raw()) }
+    }
+
+    /// Returns the local space position of sample `(x, y)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x` or `y` is not less than [`Self::sample_count`].
+    pub fn get_position(&self, x: u32, y: u32) -> Vec3 {
+        self.check_sample(x, y);
+
+        unsafe { JPC_HeightFieldShape_GetPosition(self.raw(), x, y).into_rolt() }
+    }
+
+    /// True if sample `(x, y)` is a hole in the terrain.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x` or `y` is not less than [`Self::sample_count`].
+    pub fn is_no_collision(&self, x: u32, y: u32) -> bool {
+        self.check_sample(x, y);
+
+        unsafe { JPC_HeightFieldShape_IsNoCollision(self.raw(), x, y) }
+    }
+
+    /// Size of the blocks that samples are compressed in. Regions passed to
+    /// [`Self::get_heights`] and [`Self::set_heights`] must be aligned to it.
+    pub fn block_size(&self) -> u32 {
+        unsafe { JPC_HeightFieldShape_GetBlockSize(self.raw()) }
+    }
+
+    /// Returns the heights of the samples in `region`, row by row. Holes are
+    /// returned as [`Self::NO_COLLISION_VALUE`].
+    ///
+    /// Heights are stored compressed, so they can be slightly different from
+    /// the ones the shape was created with.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `region` is not aligned to [`Self::block_size`] or doesn't
+    /// fit in the height field.
+    pub fn get_heights(&self, region: HeightFieldRegion) -> Vec<f32> {
+        self.check_sample_region(region);
+
+        let mut heights = vec![0.0; region.len()];
+
+        unsafe {
+            JPC_HeightFieldShape_GetHeights(
+                self.raw(),
+                region.x,
+                region.y,
+                region.size_x,
+                region.size_y,
+                heights.as_mut_ptr(),
+                region.size_x as isize,
+            );
+        }
+
+        heights
+    }
+
+    /// Replaces the heights of the samples in `region`, given row by row.
+    ///
+    /// Bodies touching the edited region are not woken up. Use
+    /// [`BodyInterface::set_height_field_heights`][crate::BodyInterface::set_height_field_heights]
+    /// to do that automatically.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `region` is not aligned to [`Self::block_size`], doesn't fit
+    /// in the height field, or if `heights` doesn't have one value for each
+    /// sample in `region`.
+    ///
+    /// # Safety
+    ///
+    /// `temp_allocator` must be valid, and the shape must not be used by a
+    /// physics update or query running on another thread.
+    pub unsafe fn set_heights(
+        &self,
+        region: HeightFieldRegion,
+        heights: &[f32],
+        temp_allocator: *mut JPC_TempAllocatorImpl,
+    ) {
+        self.check_sample_region(region);
+        assert_eq!(
+            heights.len(),
+            region.len(),
+            "expected one height for each sample in the region"
+        );
+
+        JPC_HeightFieldShape_SetHeights(
+            self.raw().cast_mut(),
+            region.x,
+            region.y,
+            region.size_x,
+            region.size_y,
+            heights.as_ptr(),
+            region.size_x as isize,
+            temp_allocator,
+        );
+    }
+
+    /// Returns the material indices of the cells in `region`, row by row.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `region` doesn't fit in the `(sample_count - 1)²` cells of the
+    /// height field.
+    pub fn get_materials(&self, region: HeightFieldRegion) -> Vec<u8> {
+        self.check_cell_region(region);
+
+        let mut materials = vec![0; region.len()];
+
+        unsafe {
+            JPC_HeightFieldShape_GetMaterials(
+                self.raw(),
+                region.x,
+                region.y,
+                region.size_x,
+                region.size_y,
+                materials.as_mut_ptr(),
+                region.size_x as isize,
+            );
+        }
+
+        materials
+    }
+
+    /// Replaces the material indices of the cells in `region`, given row by
+    /// row. If `materials` is given, it replaces the material list of the
+    /// height field that the indices refer to.
+    ///
+    /// Bodies touching the edited region are not woken up. Use
+    /// [`BodyInterface::set_height_field_materials`][crate::BodyInterface::set_height_field_materials]
+    /// to do that automatically.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `region` doesn't fit in the `(sample_count - 1)²` cells of the
+    /// height field, or if `indices` doesn't have one value for each cell in
+    /// `region`.
+    ///
+    /// # Safety
+    ///
+    /// `temp_allocator` must be valid, and the shape must not be used by a
+    /// physics update or query running on another thread.
+    pub unsafe fn set_materials(
+        &self,
+        region: HeightFieldRegion,
+        indices: &[u8],
+        materials: Option<&[*const JPC_PhysicsMaterial]>,
+        temp_allocator: *mut JPC_TempAllocatorImpl,
+    ) -> Result<(), ShapeError> {
+        self.check_cell_region(region);
+        assert_eq!(
+            indices.len(),
+            region.len(),
+            "expected one material index for each cell in the region"
+        );
+
+        let (materials_ptr, materials_len) = match materials {
+            Some(materials) => (materials.as_ptr(), materials.len()),
+            None => (std::ptr::null(), 0),
+        };
+
+        let success = JPC_HeightFieldShape_SetMaterials(
+            self.raw().cast_mut(),
+            region.x,
+            region.y,
+            region.size_x,
+            region.size_y,
+            indices.as_ptr(),
+            region.size_x as isize,
+            materials_ptr,
+            materials_len,
+            temp_allocator,
+        );
+
+        if success {
+            Ok(())
+        } else {
+            Err(ShapeError::Invalid(
+                "height fields support at most 256 materials".to_owned(),
+            ))
+        }
+    }
+
+    /// Returns the local space bounds of the terrain affected by editing
+    /// `region`, including the triangles that share its edges.
+    pub fn region_bounds(&self, region: HeightFieldRegion) -> AABox {
+        let last = self.sample_count().saturating_sub(1);
+
+        let start = self.get_position(region.x.saturating_sub(1), region.y.saturating_sub(1));
+        let end = self.get_position(
+            (region.x + region.size_x).min(last),
+            (region.y + region.size_y).min(last),
+        );
+
+        let bounds = self.shape.local_bounds();
+
+        AABox::new(
+            Vec3::new(start.x.min(end.x), bounds.min.y, start.z.min(end.z)),
+            Vec3::new(start.x.max(end.x), bounds.max.y, start.z.max(end.z)),
+        )
+    }
+
+    /// Returns the surface normal of the triangle identified by `id` at a
+    /// local space position, like a hit reported by a ray cast.
+    pub fn get_surface_normal(&self, id: SubShapeId, local_position: Vec3) -> Vec3 {
+        unsafe {
+            JPC_Shape_GetSurfaceNormal(self.shape.raw(), id.raw(), local_position.into_jolt())
+                .into_rolt()
+        }
+    }
+
+    pub fn as_shape(&self) -> &Shape {
+        &self.shape
+    }
+
+    pub fn raw(&self) -> *const JPC_HeightFieldShape {
+        self.shape.raw().cast::<JPC_HeightFieldShape>()
+    }
+
+    fn check_sample_region(&self, region: HeightFieldRegion) {
+        let block_size = self.block_size();
+        let sample_count = self.sample_count();
+
+        assert!(
+            [region.x, region.y, region.size_x, region.size_y]
+                .iter()
+                .all(|value| value % block_size == 0),
+            "{region:?} is not aligned to the block size of {block_size}"
+        );
+        assert!(
+            region.x + region.size_x <= sample_count && region.y + region.size_y <= sample_count,
+            "{region:?} is out of bounds for a height field with {sample_count} samples per side"
+        );
+    }
+
+    fn check_cell_region(&self, region: HeightFieldRegion) {
+        let cell_count = self.sample_count().saturating_sub(1);
+
+        assert!(
+            region.x + region.size_x <= cell_count && region.y + region.size_y <= cell_count,
+            "{region:?} is out of bounds for a height field with {cell_count} cells per side"
+        );
+    }
+
+    fn check_sample(&self, x: u32, y: u32) {
+        let sample_count = self.sample_count();
+
+        assert!(
+            x < sample_count && y < sample_count,
+            "sample ({x}, {y}) is out of bounds for a height field with {sample_count} samples per side"
+        );
+    }
+}
+
+/// A rectangular region of samples or cells in a [`HeightFieldShape`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeightFieldRegion {
+    pub x: u32,
+    pub y: u32,
+    pub size_x: u32,
+    pub size_y: u32,
+}
+
+impl HeightFieldRegion {
+    pub fn new(x: u32, y: u32, size_x: u32, size_y: u32) -> Self {
+        Self {
+            x,
+            y,
+            size_x,
+            size_y,
+        }
+    }
+
+    /// Number of samples or cells in the region.
+    pub fn len(&self) -> usize {
+        self.size_x as usize * self.size_y as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
@@ -0,0 +1,216 @@
+use std::any::Any;
+use std::fmt;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use joltc_sys::*;
+
+use crate::{
+    BroadPhaseLayer, BroadPhaseLayerInterface, ObjectLayer, ObjectLayerPairFilter,
+    ObjectVsBroadPhaseLayerFilter,
+};
+
+/// Checks that layer interfaces are consistent for the object layers below
+/// `num_object_layers`, by calling them for every layer and pair of layers.
+///
+/// Jolt asserts or misbehaves when these are wrong, often far away from the
+/// mistake, and a panic inside one of them aborts the process. This turns
+/// those mistakes into an error that names the layers involved.
+///
+/// This calls the pair filter for every pair of object layers, so it is meant
+/// for tables with a handful of layers, not for every possible mask-based
+/// layer.
+pub fn validate_layers(
+    num_object_layers: u32,
+    broad_phase_layer_interface: &impl BroadPhaseLayerInterface,
+    object_vs_broad_phase_layer_filter: &impl ObjectVsBroadPhaseLayerFilter,
+    object_layer_pair_filter: &impl ObjectLayerPairFilter,
+) -> Result<(), LayerValidationError> {
+    let num_broad_phase_layers = call("get_num_broad_phase_layers", None, || {
+        broad_phase_layer_interface.get_num_broad_phase_layers()
+    })?;
+
+    // The maximum value of a broad phase layer is reserved as an invalid layer.
+    if num_broad_phase_layers == 0 || num_broad_phase_layers > JPC_BroadPhaseLayer::MAX as u32 {
+        return Err(LayerValidationError::InvalidNumBroadPhaseLayers(
+            num_broad_phase_layers,
+        ));
+    }
+
+    // `JPC_ObjectLayer` is already a u32 with the object-layer-u32 feature.
+    #[allow(clippy::unnecessary_cast)]
+    let num_object_layers = num_object_layers.min(JPC_ObjectLayer::MAX as u32);
+    let object_layers = || (0..num_object_layers).map(|i| ObjectLayer::new(i as JPC_ObjectLayer));
+
+    let mut broad_phase_layers = Vec::with_capacity(num_object_layers as usize);
+    for object_layer in object_layers() {
+        let broad_phase_layer = call("get_broad_phase_layer", Some(object_layer), || {
+            broad_phase_layer_interface.get_broad_phase_layer(object_layer)
+        })?;
+
+        if broad_phase_layer.raw() as u32 >= num_broad_phase_layers {
+            return Err(LayerValidationError::BroadPhaseLayerOutOfRange {
+                object_layer,
+                broad_phase_layer,
+                num_broad_phase_layers,
+            });
+        }
+
+        broad_phase_layers.push(broad_phase_layer);
+    }
+
+    for layer1 in object_layers() {
+        for layer2 in object_layers() {
+            let collides = call(
+                "ObjectLayerPairFilter::should_collide",
+                Some(layer1),
+                || object_layer_pair_filter.should_collide(layer1, layer2),
+            )?;
+
+            if layer2.raw() > layer1.raw() {
+                let reverse = call(
+                    "ObjectLayerPairFilter::should_collide",
+                    Some(layer2),
+                    || object_layer_pair_filter.should_collide(layer2, layer1),
+                )?;
+
+                if collides != reverse {
+                    return Err(LayerValidationError::AsymmetricPairFilter { layer1, layer2 });
+                }
+            }
+
+            // Pairs that are filtered out in the broad phase never reach the
+            // pair filter, so they would silently not collide.
+            let broad_phase_layer = broad_phase_layers[layer2.raw() as usize];
+            let broad_phase_collides = call(
+                "ObjectVsBroadPhaseLayerFilter::should_collide",
+                Some(layer1),
+                || object_vs_broad_phase_layer_filter.should_collide(layer1, broad_phase_layer),
+            )?;
+
+            if collides && !broad_phase_collides {
+                return Err(LayerValidationError::BroadPhaseFilterMismatch {
+                    layer1,
+                    layer2,
+                    broad_phase_layer,
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Calls a user's layer interface method, turning a panic into an error.
+fn call<R>(
+    method: &'static str,
+    object_layer: Option<ObjectLayer>,
+    f: impl FnOnce() -> R,
+) -> Result<R, LayerValidationError> {
+    catch_unwind(AssertUnwindSafe(f)).map_err(|payload| LayerValidationError::Panicked {
+        method,
+        object_layer,
+        message: panic_message(payload.as_ref()),
+    })
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        (*message).to_owned()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_owned()
+    }
+}
+
+/// An inconsistency found by [`validate_layers`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LayerValidationError {
+    /// The number of broad phase layers is zero or too large.
+    InvalidNumBroadPhaseLayers(u32),
+
+    /// An object layer was mapped to a broad phase layer that doesn't exist.
+    BroadPhaseLayerOutOfRange {
+        object_layer: ObjectLayer,
+        broad_phase_layer: BroadPhaseLayer,
+        num_broad_phase_layers: u32,
+    },
+
+    /// The pair filter gives different answers depending on the order of the
+    /// layers.
+    AsymmetricPairFilter {
+        layer1: ObjectLayer,
+        layer2: ObjectLayer,
+    },
+
+    /// Two object layers collide, but the object vs broad phase filter keeps
+    /// `layer1` from ever finding `layer2` in its broad phase layer.
+    BroadPhaseFilterMismatch {
+        layer1: ObjectLayer,
+        layer2: ObjectLayer,
+        broad_phase_layer: BroadPhaseLayer,
+    },
+
+    /// One of the interface methods panicked.
+    Panicked {
+        method: &'static str,
+        object_layer: Option<ObjectLayer>,
+        message: String,
+    },
+}
+
+impl fmt::Display for LayerValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidNumBroadPhaseLayers(count) => write!(
+                f,
+                "there must be between 1 and {} broad phase layers, got {count}",
+                JPC_BroadPhaseLayer::MAX
+            ),
+            Self::BroadPhaseLayerOutOfRange {
+                object_layer,
+                broad_phase_layer,
+                num_broad_phase_layers,
+            } => write!(
+                f,
+                "object layer {} maps to broad phase layer {}, but there are only {num_broad_phase_layers} broad phase layers",
+                object_layer.raw(),
+                broad_phase_layer.raw()
+            ),
+            Self::AsymmetricPairFilter { layer1, layer2 } => write!(
+                f,
+                "object layer pair filter gives different results for ({0}, {1}) and ({1}, {0})",
+                layer1.raw(),
+                layer2.raw()
+            ),
+            Self::BroadPhaseFilterMismatch {
+                layer1,
+                layer2,
+                broad_phase_layer,
+            } => write!(
+                f,
+                "object layers {} and {} collide, but the object vs broad phase layer filter rejects broad phase layer {} for object layer {}",
+                layer1.raw(),
+                layer2.raw(),
+                broad_phase_layer.raw(),
+                layer1.raw()
+            ),
+            Self::Panicked {
+                method,
+                object_layer: Some(object_layer),
+                message,
+            } => write!(
+                f,
+                "{method} panicked for object layer {}: {message}",
+                object_layer.raw()
+            ),
+            Self::Panicked {
+                method,
+                object_layer: None,
+                message,
+            } => write!(f, "{method} panicked: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for LayerValidationError {}
@@ -12,28 +12,74 @@
 
 use joltc_sys::*;
 
+mod allocator;
 mod body;
 mod body_interface;
+mod collide_settings;
+mod collision_group;
+mod collision_layers;
+mod compound_shape;
 mod conversions;
+mod convex_decomposition;
+mod convex_hull_shape;
+mod decorated_shape;
+mod height_field_shape;
+mod layer_validation;
+mod logging;
 mod math;
+#[cfg(feature = "mesh-import")]
+mod mesh_import;
+mod mesh_shape;
 mod narrow_phase;
+#[cfg(feature = "object-layer-u32")]
+mod object_layer_mask;
+mod panic_guard;
+mod physics_material;
+mod physics_settings;
 mod physics_system;
+mod physics_system_builder;
+mod primitive_shape;
 mod reference;
 mod remote_drop;
+mod shape;
 mod simple_types;
 mod traits;
 
+pub use crate::allocator::*;
 pub use crate::body::*;
 pub use crate::body_interface::*;
+pub use crate::collide_settings::*;
+pub use crate::collision_group::*;
+pub use crate::collision_layers::*;
+pub use crate::compound_shape::*;
 pub use crate::conversions::*;
+pub use crate::convex_decomposition::*;
+pub use crate::convex_hull_shape::*;
+pub use crate::decorated_shape::*;
+pub use crate::height_field_shape::*;
+pub use crate::layer_validation::*;
+pub use crate::logging::*;
 pub use crate::math::*;
+#[cfg(feature = "mesh-import")]
+pub use crate::mesh_import::*;
+pub use crate::mesh_shape::*;
 pub use crate::narrow_phase::*;
+#[cfg(feature = "object-layer-u32")]
+pub use crate::object_layer_mask::*;
+pub use crate::physics_material::*;
+pub use crate::physics_settings::*;
 pub use crate::physics_system::*;
+pub use crate::physics_system_builder::*;
+pub use crate::primitive_shape::*;
 pub use crate::reference::*;
+pub use crate::shape::*;
 pub use crate::simple_types::*;
 pub use crate::traits::*;
 
 /// [`JPH::RegisterDefaultAllocator`](https://jrouwe.github.io/JoltPhysicsDocs/5.1.0/_memory_8h.html#a6ae804b1b68490f6e032ef6e7d9fc93e)
+///
+/// See [`register_rust_allocator`] to allocate through Rust's global
+/// allocator instead.
 pub fn register_default_allocator() {
     unsafe {
         JPC_RegisterDefaultAllocator();
@@ -0,0 +1,152 @@
+use std::ffi::{c_char, CStr};
+
+use joltc_sys::*;
+
+use crate::panic_guard::catch_panic;
+
+/// Sends Jolt's trace output to the [`log`] crate under the `jolt` target,
+/// instead of printing it to stdout. Use `tracing-log` to see it with
+/// `tracing`.
+///
+/// With the `asserts` feature, failed assertions are also logged as errors,
+/// and then handled as described in `set_assert_handler`.
+pub fn register_log_handlers() {
+    unsafe {
+        JPC_SetTraceHandler(Some(trace));
+    }
+
+    #[cfg(feature = "asserts")]
+    asserts::register();
+}
+
+unsafe extern "C" fn trace(message: *const c_char) {
+    catch_panic((), || {
+        let message = CStr::from_ptr(message).to_string_lossy();
+        log::info!(target: "jolt", "{message}");
+    });
+}
+
+#[cfg(feature = "asserts")]
+pub use asserts::*;
+
+#[cfg(feature = "asserts")]
+mod asserts {
+    use std::ffi::{c_char, c_uint, CStr};
+    use std::fmt;
+    use std::sync::{Arc, PoisonError, RwLock};
+
+    use joltc_sys::*;
+
+    use crate::panic_guard::catch_panic;
+
+    type AssertHandler = Arc<dyn Fn(&AssertFailure<'_>) -> AssertAction + Send + Sync>;
+
+    static ASSERT_HANDLER: RwLock<Option<AssertHandler>> = RwLock::new(None);
+
+    /// What to do after one of Jolt's assertions fails.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum AssertAction {
+        /// Trigger a breakpoint, which stops the process unless a debugger is
+        /// attached. This is what Jolt does by default.
+        Break,
+
+        /// Keep going in Jolt and panic the next time rolt checks for
+        /// pending panics, the same way as a panic in a listener or filter.
+        /// That happens when an update, query, shape creation or body change
+        /// returns, so a failure in another function surfaces at the next of
+        /// those calls.
+        Panic,
+
+        /// Ignore the failure and keep going.
+        Continue,
+    }
+
+    /// A failed assertion in Jolt, passed to the handler from
+    /// [`set_assert_handler`].
+    #[derive(Debug, Clone, Copy)]
+    pub struct AssertFailure<'a> {
+        pub expression: &'a str,
+        pub message: Option<&'a str>,
+        pub file: &'a str,
+        pub line: u32,
+    }
+
+    impl fmt::Display for AssertFailure<'_> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(
+                f,
+                "{}:{}: assertion `{}` failed",
+                self.file, self.line, self.expression
+            )?;
+
+            if let Some(message) = self.message {
+                write!(f, ": {message}")?;
+            }
+
+            Ok(())
+        }
+    }
+
+    /// Decides what happens when one of Jolt's assertions fails. The failure
+    /// is logged before `handler` is called. Without a handler, Jolt breaks.
+    ///
+    /// Requires the `asserts` feature.
+    pub fn set_assert_handler(
+        handler: impl Fn(&AssertFailure<'_>) -> AssertAction + Send + Sync + 'static,
+    ) {
+        *ASSERT_HANDLER
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = Some(Arc::new(handler));
+
+        register();
+    }
+
+    pub(super) fn register() {
+        unsafe {
+            JPC_SetAssertFailedHandler(Some(assert_failed));
+        }
+    }
+
+    unsafe extern "C" fn assert_failed(
+        expression: *const c_char,
+        message: *const c_char,
+        file: *const c_char,
+        line: c_uint,
+    ) -> bool {
+        let expression = CStr::from_ptr(expression).to_string_lossy();
+        let message = (!message.is_null()).then(|| CStr::from_ptr(message).to_string_lossy());
+        let file = CStr::from_ptr(file).to_string_lossy();
+
+        let failure = AssertFailure {
+            expression: &expression,
+            message: message.as_deref(),
+            file: &file,
+            line,
+        };
+
+        let action = catch_panic(AssertAction::Break, || {
+            log::error!(target: "jolt", "{failure}");
+
+            // Cloned out of the lock so that the handler can replace itself.
+            let handler = ASSERT_HANDLER
+                .read()
+                .unwrap_or_else(PoisonError::into_inner)
+                .clone();
+
+            match handler {
+                Some(handler) => handler(&failure),
+                None => AssertAction::Break,
+            }
+        });
+
+        match action {
+            AssertAction::Break => true,
+            AssertAction::Continue => false,
+            AssertAction::Panic => {
+                let message = failure.to_string();
+                catch_panic((), || panic!("{message}"));
+                false
+            }
+        }
+    }
+}
@@ -1,11 +1,13 @@
-use joltc_sys::{JPC_Color, JPC_DVec3, JPC_Mat44, JPC_Quat, JPC_Vec3, JPC_Vec4};
+use joltc_sys::{
+    JPC_AABox, JPC_Color, JPC_DVec3, JPC_Mat44, JPC_Quat, JPC_RMat44, JPC_Vec3, JPC_Vec4,
+};
 
 /// The type used for representing world space values.
 ///
 /// Either `f32` (default) or `f64` (`double-precision` feature).
 pub use joltc_sys::Real;
 
-pub use glam::{DVec3, Mat4, Quat, Vec3, Vec4};
+pub use glam::{DVec3, Mat3, Mat4, Quat, Vec3, Vec4};
 
 use crate::{FromJolt, IntoJolt};
 
@@ -133,7 +135,109 @@ impl FromJolt for Mat4 {
     }
 }
 
+/// Converts a world space vector to single precision, for Jolt APIs that
+/// always use `f32`, like [`AABox`].
+#[cfg(feature = "double-precision")]
+pub(crate) fn rvec3_to_vec3(value: RVec3) -> Vec3 {
+    value.as_vec3()
+}
+
+/// Converts a world space vector to single precision, for Jolt APIs that
+/// always use `f32`, like [`AABox`].
+#[cfg(not(feature = "double-precision"))]
+pub(crate) fn rvec3_to_vec3(value: RVec3) -> Vec3 {
+    value
+}
+
+/// Builds a world space matrix from a rotation and a translation, like Jolt's
+/// `RMat44::sRotationTranslation`.
+// If 'double-precision' is set, there is padding in this struct
+#[allow(clippy::needless_update)]
+pub(crate) fn rmat44_rotation_translation(rotation: Quat, translation: RVec3) -> JPC_RMat44 {
+    let rotation = Mat3::from_quat(rotation);
+
+    unsafe {
+        JPC_RMat44 {
+            col: [
+                rotation.x_axis.extend(0.0).into_jolt(),
+                rotation.y_axis.extend(0.0).into_jolt(),
+                rotation.z_axis.extend(0.0).into_jolt(),
+            ],
+            col3: translation.into_jolt(),
+            ..std::mem::zeroed()
+        }
+    }
+}
+
+/// An axis-aligned bounding box.
+///
+/// See also: Jolt's [`AABox`](https://jrouwe.github.io/JoltPhysicsDocs/5.1.0/class_a_a_box.html) class.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct AABox {
+    pub min: Vec3,
+    pub max: Vec3,
+}
+
+impl AABox {
+    pub fn new(min: Vec3, max: Vec3) -> Self {
+        Self { min, max }
+    }
+
+    pub fn from_center_half_extent(center: Vec3, half_extent: Vec3) -> Self {
+        Self {
+            min: center - half_extent,
+            max: center + half_extent,
+        }
+    }
+
+    pub fn center(&self) -> Vec3 {
+        (self.min + self.max) * 0.5
+    }
+
+    pub fn extent(&self) -> Vec3 {
+        (self.max - self.min) * 0.5
+    }
+
+    /// Grows this box to also contain `other`.
+    pub fn encapsulate(&mut self, other: &AABox) {
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+    }
+
+    /// Returns the box that contains this box after rotating and then
+    /// translating it.
+    pub fn transformed(&self, rotation: Quat, translation: Vec3) -> Self {
+        let center = rotation * self.center() + translation;
+        let extent = Mat3::from_quat(rotation).abs() * self.extent();
+
+        Self::from_center_half_extent(center, extent)
+    }
+}
+
+impl IntoJolt for AABox {
+    type Jolt = JPC_AABox;
+
+    fn into_jolt(self) -> Self::Jolt {
+        JPC_AABox {
+            min: self.min.into_jolt(),
+            max: self.max.into_jolt(),
+        }
+    }
+}
+
+impl FromJolt for AABox {
+    type Jolt = JPC_AABox;
+
+    fn from_jolt(value: Self::Jolt) -> Self {
+        Self {
+            min: Vec3::from_jolt(value.min),
+            max: Vec3::from_jolt(value.max),
+        }
+    }
+}
+
 /// Represents an sRGB color with alpha.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(C)]
 pub struct Color {
     pub r: u8,
@@ -0,0 +1,375 @@
+//! Loading collision meshes from Wavefront OBJ and glTF files.
+//!
+//! Requires the `mesh-import` feature.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use base64::Engine;
+
+use crate::{
+    ConvexHullShapeSettings, IndexedTriangle, Mat4, MeshShapeSettings, PhysicsMaterialList, Vec3,
+};
+
+/// A triangle mesh loaded from a file, with the names of the materials that
+/// its triangles use.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ImportedMesh {
+    pub vertices: Vec<Vec3>,
+
+    /// Triangles of the mesh, whose material indices point into
+    /// [`Self::material_names`].
+    pub triangles: Vec<IndexedTriangle>,
+
+    /// Names of the materials used by the mesh. Triangles without a material
+    /// use an empty name.
+    pub material_names: Vec<String>,
+}
+
+impl ImportedMesh {
+    /// Loads a mesh from an OBJ, glTF or GLB file, picked by the file's
+    /// extension.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, MeshImportError> {
+        let path = path.as_ref();
+        let extension = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(|extension| extension.to_ascii_lowercase());
+
+        match extension.as_deref() {
+            Some("obj") => Self::from_obj(path),
+            Some("gltf" | "glb") => Self::from_gltf(path),
+            _ => Err(MeshImportError::UnsupportedFormat(path.to_owned())),
+        }
+    }
+
+    /// Loads all of the objects in an OBJ file into one mesh. Polygons are
+    /// triangulated, and material names are read from the file's MTL library
+    /// if it can be found.
+    pub fn from_obj(path: impl AsRef<Path>) -> Result<Self, MeshImportError> {
+        let options = tobj::LoadOptions {
+            triangulate: true,
+            single_index: true,
+            ignore_points: true,
+            ignore_lines: true,
+        };
+
+        let (models, materials) = tobj::load_obj(path.as_ref(), &options)?;
+
+        // A missing MTL library only means we don't know the material names.
+        let material_names: Vec<String> = materials
+            .map(|materials| {
+                materials
+                    .into_iter()
+                    .map(|material| material.name)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let mut mesh = Self::default();
+
+        for model in models {
+            let name = model
+                .mesh
+                .material_id
+                .and_then(|id| material_names.get(id))
+                .map(String::as_str)
+                .unwrap_or("");
+
+            let positions = model
+                .mesh
+                .positions
+                .chunks_exact(3)
+                .map(|position| Vec3::new(position[0], position[1], position[2]));
+
+            let indices = model
+                .mesh
+                .indices
+                .chunks_exact(3)
+                .map(|triangle| [triangle[0], triangle[1], triangle[2]]);
+
+            mesh.append(name, positions, indices);
+        }
+
+        Ok(mesh)
+    }
+
+    /// Loads the triangles of the default scene of a glTF or GLB file into
+    /// one mesh, with node transforms applied. If the file has no scenes,
+    /// every mesh is loaded without a transform.
+    ///
+    /// Primitives that aren't triangle lists are skipped.
+    pub fn from_gltf(path: impl AsRef<Path>) -> Result<Self, MeshImportError> {
+        let path = path.as_ref();
+        let gltf = gltf::Gltf::open(path)?;
+        let buffers = load_gltf_buffers(&gltf, path)?;
+
+        let mut mesh = Self::default();
+
+        match gltf.default_scene().or_else(|| gltf.scenes().next()) {
+            Some(scene) => {
+                for node in scene.nodes() {
+                    mesh.append_gltf_node(&node, Mat4::IDENTITY, &buffers)?;
+                }
+            }
+            None => {
+                for gltf_mesh in gltf.meshes() {
+                    mesh.append_gltf_mesh(&gltf_mesh, Mat4::IDENTITY, &buffers)?;
+                }
+            }
+        }
+
+        Ok(mesh)
+    }
+
+    /// Builds the settings for a mesh shape, assigning each triangle the
+    /// material from `materials` with the same debug name. Triangles without
+    /// a material use the first one.
+    ///
+    /// Returns [`MeshImportError::UnknownMaterial`] if the mesh uses a
+    /// material that isn't in `materials`. If `materials` is empty, every
+    /// triangle uses Jolt's default material instead.
+    pub fn to_mesh_shape_settings<T>(
+        &self,
+        materials: &PhysicsMaterialList<T>,
+    ) -> Result<MeshShapeSettings, MeshImportError> {
+        let remap = self
+            .material_names
+            .iter()
+            .map(|name| {
+                if materials.is_empty() || name.is_empty() {
+                    return Ok(0);
+                }
+
+                materials
+                    .index_of(name)
+                    .ok_or_else(|| MeshImportError::UnknownMaterial(name.clone()))
+            })
+            .collect::<Result<Vec<u32>, _>>()?;
+
+        let triangles = self
+            .triangles
+            .iter()
+            .map(|triangle| IndexedTriangle {
+                material_index: remap[triangle.material_index as usize],
+                ..*triangle
+            })
+            .collect();
+
+        let mut settings = MeshShapeSettings::new(self.vertices.clone(), triangles);
+        settings.materials = materials.raw_materials();
+        Ok(settings)
+    }
+
+    /// Builds the settings for the convex hull of all of the mesh's vertices.
+    pub fn to_convex_hull_shape_settings(&self) -> ConvexHullShapeSettings {
+        ConvexHullShapeSettings::new(self.vertices.clone())
+    }
+
+    fn material_index(&mut self, name: &str) -> u32 {
+        let index = match self.material_names.iter().position(|n| n == name) {
+            Some(index) => index,
+            None => {
+                self.material_names.push(name.to_owned());
+                self.material_names.len() - 1
+            }
+        };
+
+        index as u32
+    }
+
+    fn append(
+        &mut self,
+        material_name: &str,
+        positions: impl Iterator<Item = Vec3>,
+        triangles: impl Iterator<Item = [u32; 3]>,
+    ) {
+        let material_index = self.material_index(material_name);
+        let base = self.vertices.len() as u32;
+
+        self.vertices.extend(positions);
+        self.triangles.extend(triangles.map(|indices| {
+            IndexedTriangle::new(indices.map(|index| base + index), material_index)
+        }));
+    }
+
+    fn append_gltf_node(
+        &mut self,
+        node: &gltf::Node<'_>,
+        parent_transform: Mat4,
+        buffers: &[Vec<u8>],
+    ) -> Result<(), MeshImportError> {
+        let transform = parent_transform * Mat4::from_cols_array_2d(&node.transform().matrix());
+
+        if let Some(gltf_mesh) = node.mesh() {
+            self.append_gltf_mesh(&gltf_mesh, transform, buffers)?;
+        }
+
+        for child in node.children() {
+            self.append_gltf_node(&child, transform, buffers)?;
+        }
+
+        Ok(())
+    }
+
+    fn append_gltf_mesh(
+        &mut self,
+        gltf_mesh: &gltf::Mesh<'_>,
+        transform: Mat4,
+        buffers: &[Vec<u8>],
+    ) -> Result<(), MeshImportError> {
+        for primitive in gltf_mesh.primitives() {
+            if primitive.mode() != gltf::mesh::Mode::Triangles {
+                continue;
+            }
+
+            let reader = primitive.reader(|buffer| buffers.get(buffer.index()).map(Vec::as_slice));
+
+            let Some(positions) = reader.read_positions() else {
+                continue;
+            };
+
+            let positions: Vec<Vec3> = positions
+                .map(|position| transform.transform_point3(Vec3::from_array(position)))
+                .collect();
+
+            let indices: Vec<u32> = match reader.read_indices() {
+                Some(indices) => indices.into_u32().collect(),
+                None => (0..positions.len() as u32).collect(),
+            };
+
+            if let Some(&index) = indices.iter().find(|&&i| i as usize >= positions.len()) {
+                return Err(MeshImportError::InvalidData(format!(
+                    "index {index} is out of range for {} vertices in mesh {:?}",
+                    positions.len(),
+                    gltf_mesh.name().unwrap_or_default()
+                )));
+            }
+
+            // A mirroring transform turns the triangles inside out, so the
+            // winding has to be reversed to keep them facing the same way.
+            let mirrored = transform.determinant() < 0.0;
+
+            let material = primitive.material();
+            let triangles = indices.chunks_exact(3).map(|triangle| {
+                if mirrored {
+                    [triangle[0], triangle[2], triangle[1]]
+                } else {
+                    [triangle[0], triangle[1], triangle[2]]
+                }
+            });
+
+            self.append(
+                material.name().unwrap_or(""),
+                positions.into_iter(),
+                triangles,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+fn load_gltf_buffers(gltf: &gltf::Gltf, path: &Path) -> Result<Vec<Vec<u8>>, MeshImportError> {
+    let base_dir = path.parent().unwrap_or(Path::new(""));
+
+    gltf.buffers()
+        .map(|buffer| {
+            let data = match buffer.source() {
+                gltf::buffer::Source::Bin => gltf.blob.clone().ok_or_else(|| {
+                    MeshImportError::InvalidData("GLB file has no binary chunk".to_owned())
+                })?,
+                gltf::buffer::Source::Uri(uri) => {
+                    if let Some(data) = uri.strip_prefix("data:") {
+                        let (_, encoded) = data.split_once(";base64,").ok_or_else(|| {
+                            MeshImportError::InvalidData(
+                                "only base64 data URIs are supported".to_owned(),
+                            )
+                        })?;
+
+                        base64::engine::general_purpose::STANDARD
+                            .decode(encoded)
+                            .map_err(|err| MeshImportError::InvalidData(err.to_string()))?
+                    } else {
+                        fs::read(base_dir.join(uri))?
+                    }
+                }
+            };
+
+            if data.len() < buffer.length() {
+                return Err(MeshImportError::InvalidData(format!(
+                    "buffer {} is {} bytes long, expected {}",
+                    buffer.index(),
+                    data.len(),
+                    buffer.length()
+                )));
+            }
+
+            Ok(data)
+        })
+        .collect()
+}
+
+/// An error that happened while importing a mesh.
+#[derive(Debug)]
+pub enum MeshImportError {
+    Io(io::Error),
+    Obj(tobj::LoadError),
+    Gltf(gltf::Error),
+
+    /// The file's extension isn't one of `obj`, `gltf` or `glb`.
+    UnsupportedFormat(PathBuf),
+
+    /// The file was parsed, but its contents don't make sense.
+    InvalidData(String),
+
+    /// The mesh uses a material that isn't in the [`PhysicsMaterialList`]
+    /// passed to [`ImportedMesh::to_mesh_shape_settings`].
+    UnknownMaterial(String),
+}
+
+impl fmt::Display for MeshImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "could not read mesh: {err}"),
+            Self::Obj(err) => write!(f, "could not load OBJ mesh: {err}"),
+            Self::Gltf(err) => write!(f, "could not load glTF mesh: {err}"),
+            Self::UnsupportedFormat(path) => {
+                write!(f, "unsupported mesh format: {}", path.display())
+            }
+            Self::InvalidData(message) => write!(f, "invalid mesh: {message}"),
+            Self::UnknownMaterial(name) => write!(f, "unknown mesh material: {name:?}"),
+        }
+    }
+}
+
+impl std::error::Error for MeshImportError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Obj(err) => Some(err),
+            Self::Gltf(err) => Some(err),
+            Self::UnsupportedFormat(_) | Self::InvalidData(_) | Self::UnknownMaterial(_) => None,
+        }
+    }
+}
+
+impl From<io::Error> for MeshImportError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<tobj::LoadError> for MeshImportError {
+    fn from(err: tobj::LoadError) -> Self {
+        Self::Obj(err)
+    }
+}
+
+impl From<gltf::Error> for MeshImportError {
+    fn from(err: gltf::Error) -> Self {
+        Self::Gltf(err)
+    }
+}
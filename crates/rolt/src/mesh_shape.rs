@@ -0,0 +1,121 @@
+use joltc_sys::*;
+
+use crate::shape::create_shape;
+use crate::{IntoJolt, Shape, ShapeError, Vec3};
+
+/// A triangle of a [`MeshShapeSettings`], made of indices into its vertices.
+///
+/// See also: Jolt's [`IndexedTriangle`](https://jrouwe.github.io/JoltPhysicsDocs/5.1.0/class_indexed_triangle.html) class.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct IndexedTriangle {
+    pub indices: [u32; 3],
+
+    /// Index into [`MeshShapeSettings::materials`].
+    pub material_index: u32,
+    pub user_data: u32,
+}
+
+impl IndexedTriangle {
+    pub fn new(indices: [u32; 3], material_index: u32) -> Self {
+        Self {
+            indices,
+            material_index,
+            user_data: 0,
+        }
+    }
+}
+
+impl IntoJolt for IndexedTriangle {
+    type Jolt = JPC_IndexedTriangle;
+
+    fn into_jolt(self) -> Self::Jolt {
+        JPC_IndexedTriangle {
+            idx: self.indices,
+            materialIndex: self.material_index,
+            userData: self.user_data,
+        }
+    }
+}
+
+/// Settings for a shape made of triangles. Mesh shapes can only be used by
+/// static and kinematic bodies.
+///
+/// See also: Jolt's [`MeshShapeSettings`](https://jrouwe.github.io/JoltPhysicsDocs/5.1.0/class_mesh_shape_settings.html) class.
+#[derive(Debug, Clone)]
+pub struct MeshShapeSettings {
+    pub user_data: u64,
+    pub vertices: Vec<Vec3>,
+    pub triangles: Vec<IndexedTriangle>,
+
+    /// Materials that triangles refer to by index, like the ones from
+    /// [`PhysicsMaterialList::raw_materials`][crate::PhysicsMaterialList::raw_materials].
+    /// If empty, every triangle uses the default material.
+    pub materials: Vec<*const JPC_PhysicsMaterial>,
+    pub max_triangles_per_leaf: u32,
+    pub active_edge_cos_threshold_angle: f32,
+
+    /// Whether to store [`IndexedTriangle::user_data`] in the shape.
+    pub per_triangle_user_data: bool,
+}
+
+impl MeshShapeSettings {
+    pub fn new(vertices: Vec<Vec3>, triangles: Vec<IndexedTriangle>) -> Self {
+        let defaults = JPC_MeshShapeSettings::default();
+
+        Self {
+            user_data: 0,
+            vertices,
+            triangles,
+            materials: Vec::new(),
+            max_triangles_per_leaf: defaults.MaxTrianglesPerLeaf,
+            active_edge_cos_threshold_angle: defaults.ActiveEdgeCosThresholdAngle,
+            per_triangle_user_data: defaults.PerTriangleUserData,
+        }
+    }
+
+    pub fn create(&self) -> Result<Shape, ShapeError> {
+        let vertex_count = self.vertices.len();
+        if let Some(triangle) = self
+            .triangles
+            .iter()
+            .find(|triangle| triangle.indices.iter().any(|&i| i as usize >= vertex_count))
+        {
+            return Err(ShapeError::Invalid(format!(
+                "triangle {:?} refers to a vertex out of range for {vertex_count} vertices",
+                triangle.indices
+            )));
+        }
+
+        let vertices: Vec<JPC_Float3> = self
+            .vertices
+            .iter()
+            .map(|vertex| JPC_Float3 {
+                x: vertex.x,
+                y: vertex.y,
+                z: vertex.z,
+            })
+            .collect();
+
+        let triangles: Vec<JPC_IndexedTriangle> = self
+            .triangles
+            .iter()
+            .map(|triangle| triangle.into_jolt())
+            .collect();
+
+        let settings = JPC_MeshShapeSettings {
+            UserData: self.user_data,
+            TriangleVertices: vertices.as_ptr(),
+            TriangleVerticesLen: vertices.len(),
+            IndexedTriangles: triangles.as_ptr(),
+            IndexedTrianglesLen: triangles.len(),
+            Materials: self.materials.as_ptr(),
+            MaterialsLen: self.materials.len(),
+            MaxTrianglesPerLeaf: self.max_triangles_per_leaf,
+            ActiveEdgeCosThresholdAngle: self.active_edge_cos_threshold_angle,
+            PerTriangleUserData: self.per_triangle_user_data,
+            ..Default::default()
+        };
+
+        create_shape(|shape, err| unsafe { JPC_MeshShapeSettings_Create(&settings, shape, err) })
+    }
+}
@@ -3,12 +3,20 @@ use std::mem;
 
 use joltc_sys::*;
 
+use crate::math::rmat44_rotation_translation;
+use crate::panic_guard::checked_call;
 use crate::{
-    BodyFilterImpl, BodyId, BroadPhaseLayerFilterImpl, CastShapeBase, CastShapeCollector,
-    CastShapeCollectorImpl, CollideShapeBase, CollideShapeCollector, CollideShapeCollectorImpl,
-    FromJolt, IntoJolt, ObjectLayerFilterImpl, RVec3, ShapeFilterImpl, Vec3,
+    AABox, BodyFilterImpl, BodyId, BroadPhaseLayerFilterImpl, CastShapeBase, CastShapeCollector,
+    CastShapeCollectorImpl, CollidePointBase, CollidePointCollector, CollidePointCollectorImpl,
+    CollideShapeBase, CollideShapeCollector, CollideShapeCollectorImpl, CollideShapeSettings,
+    FromJolt, IntoJolt, ObjectLayerFilterImpl, Quat, RVec3, Shape, ShapeCastSettings,
+    ShapeFilterImpl, SubShapeId, TransformedShape, TransformedShapeBase, TransformedShapeCollector,
+    TransformedShapeCollectorImpl, Vec3,
 };
 
+/// Can be shared between threads to run queries in parallel. The filters and
+/// collectors passed to a query are only called on the thread running it.
+///
 /// See also: Jolt's [`NarrowPhaseQuery`](https://jrouwe.github.io/JoltPhysicsDocs/5.1.0/class_narrow_phase_query.html) class.
 pub struct NarrowPhaseQuery<'physics_system> {
     raw: *const JPC_NarrowPhaseQuery,
@@ -50,7 +58,7 @@ pub struct RayCastArgs<'a> {
 pub struct RayCastResult {
     pub body_id: BodyId,
     pub fraction: f32,
-    pub sub_shape_id: JPC_SubShapeID,
+    pub sub_shape_id: SubShapeId,
 }
 
 impl FromJolt for RayCastResult {
@@ -60,11 +68,135 @@ impl FromJolt for RayCastResult {
         Self {
             body_id: BodyId::new(value.BodyID),
             fraction: value.Fraction,
-            sub_shape_id: value.SubShapeID2,
+            sub_shape_id: SubShapeId::new(value.SubShapeID2),
         }
     }
 }
 
+/// A hit found by a shape collision query, like
+/// [`NarrowPhaseQuery::collide_shape_all`].
+///
+/// Shape 1 is the query shape and shape 2 is the shape that was hit. All
+/// positions are relative to the base offset of the query.
+///
+/// See also: Jolt's [`CollideShapeResult`](https://jrouwe.github.io/JoltPhysicsDocs/5.1.0/class_collide_shape_result.html) class.
+#[derive(Debug, Clone)]
+pub struct CollideShapeHit {
+    pub contact_point_on_1: Vec3,
+    pub contact_point_on_2: Vec3,
+
+    /// Direction to move shape 2 out of collision along the shortest path.
+    pub penetration_axis: Vec3,
+    pub penetration_depth: f32,
+    pub sub_shape_id_1: SubShapeId,
+    pub sub_shape_id_2: SubShapeId,
+    pub body_id: BodyId,
+
+    /// Colliding face on shape 1. Only filled in when
+    /// [`CollectFacesMode::CollectFaces`][crate::CollectFacesMode::CollectFaces]
+    /// is used.
+    pub shape_1_face: Option<Vec<Vec3>>,
+
+    /// Colliding face on shape 2. Only filled in when
+    /// [`CollectFacesMode::CollectFaces`][crate::CollectFacesMode::CollectFaces]
+    /// is used.
+    pub shape_2_face: Option<Vec<Vec3>>,
+}
+
+impl FromJolt for CollideShapeHit {
+    type Jolt = JPC_CollideShapeResult;
+
+    fn from_jolt(value: Self::Jolt) -> Self {
+        Self {
+            contact_point_on_1: Vec3::from_jolt(value.ContactPointOn1),
+            contact_point_on_2: Vec3::from_jolt(value.ContactPointOn2),
+            penetration_axis: Vec3::from_jolt(value.PenetrationAxis),
+            penetration_depth: value.PenetrationDepth,
+            sub_shape_id_1: SubShapeId::new(value.SubShapeID1),
+            sub_shape_id_2: SubShapeId::new(value.SubShapeID2),
+            body_id: BodyId::new(value.BodyID2),
+            shape_1_face: face_from_jolt(&value.Shape1Face),
+            shape_2_face: face_from_jolt(&value.Shape2Face),
+        }
+    }
+}
+
+/// A hit found by a shape cast, like [`NarrowPhaseQuery::cast_shape_closest`].
+///
+/// Shape 1 is the cast shape and shape 2 is the shape that was hit. All
+/// positions are relative to the base offset of the query.
+///
+/// See also: Jolt's [`ShapeCastResult`](https://jrouwe.github.io/JoltPhysicsDocs/5.1.0/class_shape_cast_result.html) class.
+#[derive(Debug, Clone)]
+pub struct ShapeCastHit {
+    pub contact_point_on_1: Vec3,
+    pub contact_point_on_2: Vec3,
+
+    /// Direction to move shape 2 out of collision along the shortest path.
+    pub penetration_axis: Vec3,
+    pub penetration_depth: f32,
+    pub sub_shape_id_1: SubShapeId,
+    pub sub_shape_id_2: SubShapeId,
+    pub body_id: BodyId,
+
+    /// Colliding face on shape 1. Only filled in when
+    /// [`CollectFacesMode::CollectFaces`][crate::CollectFacesMode::CollectFaces]
+    /// is used.
+    pub shape_1_face: Option<Vec<Vec3>>,
+
+    /// Colliding face on shape 2. Only filled in when
+    /// [`CollectFacesMode::CollectFaces`][crate::CollectFacesMode::CollectFaces]
+    /// is used.
+    pub shape_2_face: Option<Vec<Vec3>>,
+
+    /// Fraction of the cast direction at which the hit occurred.
+    pub fraction: f32,
+
+    /// True if the shape was hit from the back side.
+    pub is_back_face_hit: bool,
+}
+
+impl ShapeCastHit {
+    /// The value a collector should use for early outs, which also orders
+    /// hits that were already penetrating at the start of the cast.
+    pub fn early_out_fraction(&self) -> f32 {
+        if self.fraction > 0.0 {
+            self.fraction
+        } else {
+            -self.penetration_depth
+        }
+    }
+}
+
+impl FromJolt for ShapeCastHit {
+    type Jolt = JPC_ShapeCastResult;
+
+    fn from_jolt(value: Self::Jolt) -> Self {
+        Self {
+            contact_point_on_1: Vec3::from_jolt(value.ContactPointOn1),
+            contact_point_on_2: Vec3::from_jolt(value.ContactPointOn2),
+            penetration_axis: Vec3::from_jolt(value.PenetrationAxis),
+            penetration_depth: value.PenetrationDepth,
+            sub_shape_id_1: SubShapeId::new(value.SubShapeID1),
+            sub_shape_id_2: SubShapeId::new(value.SubShapeID2),
+            body_id: BodyId::new(value.BodyID2),
+            shape_1_face: face_from_jolt(&value.Shape1Face),
+            shape_2_face: face_from_jolt(&value.Shape2Face),
+            fraction: value.Fraction,
+            is_back_face_hit: value.IsBackFaceHit,
+        }
+    }
+}
+
+fn face_from_jolt(face: &JPC_Face) -> Option<Vec<Vec3>> {
+    if face.PointsLen == 0 {
+        return None;
+    }
+
+    let points = &face.Points[..face.PointsLen as usize];
+    Some(points.iter().copied().map(Vec3::from_jolt).collect())
+}
+
 pub struct RShapeCast {
     pub shape: *const JPC_Shape,
     pub scale: Vec3,
@@ -87,7 +219,7 @@ pub struct CastShapeArgs<'a> {
 #[non_exhaustive]
 #[derive(Default)]
 pub struct ClosestHitCastShapeCollector {
-    pub result: Option<JPC_ShapeCastResult>,
+    pub result: Option<ShapeCastHit>,
 }
 
 impl ClosestHitCastShapeCollector {
@@ -114,12 +246,13 @@ impl CastShapeCollector for ClosestHitCastShapeCollector {
 
         let set = self
             .result
-            .map(|old| early_out < early_out_fraction(&old))
+            .as_ref()
+            .map(|old| early_out < old.early_out_fraction())
             .unwrap_or(true);
 
         if set {
             base.update_early_out_fraction(early_out);
-            self.result = Some(*result);
+            self.result = Some(ShapeCastHit::from_jolt(*result));
         }
     }
 }
@@ -140,7 +273,7 @@ pub struct CollideShapeArgs<'a> {
 #[non_exhaustive]
 #[derive(Default)]
 pub struct AllHitCollideShapeCollector {
-    pub result: Vec<JPC_CollideShapeResult>,
+    pub result: Vec<CollideShapeHit>,
 }
 
 impl AllHitCollideShapeCollector {
@@ -155,10 +288,206 @@ impl CollideShapeCollector for AllHitCollideShapeCollector {
     }
 
     fn add_hit(&mut self, _base: &mut CollideShapeBase, result: &JPC_CollideShapeResult) {
-        self.result.push(*result);
+        self.result.push(CollideShapeHit::from_jolt(*result));
+    }
+}
+
+/// Arguments for [`NarrowPhaseQuery::cast_shape_with`] and
+/// [`NarrowPhaseQuery::cast_shape_closest`].
+pub struct ShapeCastQuery<'a> {
+    pub shape: &'a Shape,
+    pub scale: Vec3,
+
+    /// World space position of the shape's center of mass at the start of the
+    /// cast.
+    pub position: RVec3,
+    pub rotation: Quat,
+
+    /// Direction and length of the cast. Anything beyond this length will not
+    /// be reported as a hit.
+    pub direction: Vec3,
+
+    /// All hit results will be returned relative to this offset.
+    pub base_offset: RVec3,
+    pub settings: ShapeCastSettings,
+    pub broad_phase_layer_filter: Option<BroadPhaseLayerFilterImpl<'a>>,
+    pub object_layer_filter: Option<ObjectLayerFilterImpl<'a>>,
+    pub body_filter: Option<BodyFilterImpl<'a>>,
+    pub shape_filter: Option<ShapeFilterImpl<'a>>,
+}
+
+impl<'a> ShapeCastQuery<'a> {
+    /// Creates a query that casts `shape` from `position` along `direction`,
+    /// with the default settings and no filters.
+    pub fn new(shape: &'a Shape, position: RVec3, rotation: Quat, direction: Vec3) -> Self {
+        Self {
+            shape,
+            scale: Vec3::ONE,
+            position,
+            rotation,
+            direction,
+            base_offset: RVec3::ZERO,
+            settings: ShapeCastSettings::default(),
+            broad_phase_layer_filter: None,
+            object_layer_filter: None,
+            body_filter: None,
+            shape_filter: None,
+        }
     }
 }
 
+/// Arguments for [`NarrowPhaseQuery::collide_shape_with`] and
+/// [`NarrowPhaseQuery::collide_shape_all`].
+pub struct CollideShapeQuery<'a> {
+    pub shape: &'a Shape,
+    pub scale: Vec3,
+
+    /// World space position of the shape's center of mass.
+    pub position: RVec3,
+    pub rotation: Quat,
+
+    /// All hit results will be returned relative to this offset.
+    pub base_offset: RVec3,
+    pub settings: CollideShapeSettings,
+    pub broad_phase_layer_filter: Option<BroadPhaseLayerFilterImpl<'a>>,
+    pub object_layer_filter: Option<ObjectLayerFilterImpl<'a>>,
+    pub body_filter: Option<BodyFilterImpl<'a>>,
+    pub shape_filter: Option<ShapeFilterImpl<'a>>,
+}
+
+impl<'a> CollideShapeQuery<'a> {
+    /// Creates a query that collides `shape` at `position`, with the default
+    /// settings and no filters.
+    pub fn new(shape: &'a Shape, position: RVec3, rotation: Quat) -> Self {
+        Self {
+            shape,
+            scale: Vec3::ONE,
+            position,
+            rotation,
+            base_offset: RVec3::ZERO,
+            settings: CollideShapeSettings::default(),
+            broad_phase_layer_filter: None,
+            object_layer_filter: None,
+            body_filter: None,
+            shape_filter: None,
+        }
+    }
+}
+
+/// Arguments for [`NarrowPhaseQuery::collide_point`].
+pub struct CollidePointArgs<'a> {
+    pub point: RVec3,
+    pub collector: CollidePointCollectorImpl<'a>,
+    pub broad_phase_layer_filter: Option<BroadPhaseLayerFilterImpl<'a>>,
+    pub object_layer_filter: Option<ObjectLayerFilterImpl<'a>>,
+    pub body_filter: Option<BodyFilterImpl<'a>>,
+    pub shape_filter: Option<ShapeFilterImpl<'a>>,
+}
+
+/// A single hit reported to a [`CollidePointCollector`].
+#[derive(Debug, Clone, Copy)]
+pub struct CollidePointResult {
+    pub body_id: BodyId,
+    pub sub_shape_id: SubShapeId,
+}
+
+impl FromJolt for CollidePointResult {
+    type Jolt = JPC_CollidePointResult;
+
+    fn from_jolt(value: Self::Jolt) -> Self {
+        Self {
+            body_id: BodyId::new(value.BodyID),
+            sub_shape_id: SubShapeId::new(value.SubShapeID2),
+        }
+    }
+}
+
+/// Collects every shape that contains the point.
+#[non_exhaustive]
+#[derive(Default)]
+pub struct AllHitCollidePointCollector {
+    pub result: Vec<CollidePointResult>,
+}
+
+impl AllHitCollidePointCollector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl CollidePointCollector for AllHitCollidePointCollector {
+    fn reset(&mut self) {
+        self.result.clear();
+    }
+
+    fn add_hit(&mut self, _base: &mut CollidePointBase, result: &JPC_CollidePointResult) {
+        self.result.push(CollidePointResult::from_jolt(*result));
+    }
+}
+
+/// Stops the query as soon as any shape containing the point is found.
+#[non_exhaustive]
+#[derive(Default)]
+pub struct AnyHitCollidePointCollector {
+    pub result: Option<CollidePointResult>,
+}
+
+impl AnyHitCollidePointCollector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl CollidePointCollector for AnyHitCollidePointCollector {
+    fn reset(&mut self) {
+        self.result = None;
+    }
+
+    fn add_hit(&mut self, base: &mut CollidePointBase, result: &JPC_CollidePointResult) {
+        // Equivalent to Jolt's ForceEarlyOut
+        base.update_early_out_fraction(f32::MIN);
+        self.result = Some(CollidePointResult::from_jolt(*result));
+    }
+}
+
+/// Arguments for [`NarrowPhaseQuery::collect_transformed_shapes`].
+#[derive(Default)]
+pub struct CollectTransformedShapesArgs<'a> {
+    pub bounds: AABox,
+    pub broad_phase_layer_filter: Option<BroadPhaseLayerFilterImpl<'a>>,
+    pub object_layer_filter: Option<ObjectLayerFilterImpl<'a>>,
+    pub body_filter: Option<BodyFilterImpl<'a>>,
+    pub shape_filter: Option<ShapeFilterImpl<'a>>,
+}
+
+/// Collects every leaf shape that overlaps the query box.
+#[non_exhaustive]
+#[derive(Default)]
+pub struct AllHitTransformedShapeCollector {
+    pub result: Vec<TransformedShape>,
+}
+
+impl AllHitTransformedShapeCollector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl TransformedShapeCollector for AllHitTransformedShapeCollector {
+    fn reset(&mut self) {
+        self.result.clear();
+    }
+
+    fn add_hit(&mut self, _base: &mut TransformedShapeBase, result: TransformedShape) {
+        self.result.push(result);
+    }
+}
+
+// SAFETY: Jolt's narrow phase queries lock the bodies they touch, and only read
+// from the query object itself.
+unsafe impl Send for NarrowPhaseQuery<'_> {}
+unsafe impl Sync for NarrowPhaseQuery<'_> {}
+
 impl<'physics_system> NarrowPhaseQuery<'physics_system> {
     pub(crate) fn new(raw: *const JPC_NarrowPhaseQuery) -> Self {
         Self {
@@ -177,7 +506,7 @@ impl<'physics_system> NarrowPhaseQuery<'physics_system> {
             ShapeFilter: args.shape_filter.as_ref().into_jolt(),
         };
 
-        let hit = unsafe { JPC_NarrowPhaseQuery_CastRay(self.raw, &mut raw_args) };
+        let hit = checked_call(|| unsafe { JPC_NarrowPhaseQuery_CastRay(self.raw, &mut raw_args) });
 
         if hit {
             Some(RayCastResult::from_jolt(raw_args.Result))
@@ -206,7 +535,7 @@ impl<'physics_system> NarrowPhaseQuery<'physics_system> {
             ..mem::zeroed()
         };
 
-        JPC_NarrowPhaseQuery_CastShape(self.raw, &mut raw_args);
+        checked_call(|| JPC_NarrowPhaseQuery_CastShape(self.raw, &mut raw_args));
     }
 
     pub unsafe fn collide_shape(&self, args: CollideShapeArgs<'_>) {
@@ -224,7 +553,123 @@ impl<'physics_system> NarrowPhaseQuery<'physics_system> {
             ..mem::zeroed()
         };
 
-        JPC_NarrowPhaseQuery_CollideShape(self.raw, &mut raw_args);
+        checked_call(|| JPC_NarrowPhaseQuery_CollideShape(self.raw, &mut raw_args));
+    }
+
+    /// Sweeps a shape through the world, reporting every hit to `collector`.
+    pub fn cast_shape_with<C: CastShapeCollector>(
+        &self,
+        query: ShapeCastQuery<'_>,
+        collector: &mut C,
+    ) {
+        let collector = CastShapeCollectorImpl::new_borrowed(collector);
+
+        let mut raw_args = JPC_NarrowPhaseQuery_CastShapeArgs {
+            ShapeCast: JPC_RShapeCast {
+                Shape: query.shape.raw(),
+                Scale: query.scale.into_jolt(),
+                CenterOfMassStart: rmat44_rotation_translation(query.rotation, query.position),
+                Direction: query.direction.into_jolt(),
+                // const JPC_AABox ShapeWorldBounds;
+                ..unsafe { mem::zeroed() }
+            },
+            Settings: query.settings.into_jolt(),
+            BaseOffset: query.base_offset.into_jolt(),
+            Collector: collector.raw(),
+            BroadPhaseLayerFilter: query.broad_phase_layer_filter.as_ref().into_jolt(),
+            ObjectLayerFilter: query.object_layer_filter.as_ref().into_jolt(),
+            BodyFilter: query.body_filter.as_ref().into_jolt(),
+            ShapeFilter: query.shape_filter.as_ref().into_jolt(),
+            ..unsafe { mem::zeroed() }
+        };
+
+        checked_call(|| unsafe {
+            JPC_NarrowPhaseQuery_CastShape(self.raw, &mut raw_args);
+        });
+    }
+
+    /// Sweeps a shape through the world and returns the closest hit, if any.
+    pub fn cast_shape_closest(&self, query: ShapeCastQuery<'_>) -> Option<ShapeCastHit> {
+        let mut collector = ClosestHitCastShapeCollector::new();
+        self.cast_shape_with(query, &mut collector);
+        collector.result
+    }
+
+    /// Collides a shape with the world, reporting every hit to `collector`.
+    pub fn collide_shape_with<C: CollideShapeCollector>(
+        &self,
+        query: CollideShapeQuery<'_>,
+        collector: &mut C,
+    ) {
+        let collector = CollideShapeCollectorImpl::new_borrowed(collector);
+
+        let mut raw_args = JPC_NarrowPhaseQuery_CollideShapeArgs {
+            Shape: query.shape.raw(),
+            ShapeScale: query.scale.into_jolt(),
+            CenterOfMassTransform: rmat44_rotation_translation(query.rotation, query.position),
+            Settings: query.settings.into_jolt(),
+            BaseOffset: query.base_offset.into_jolt(),
+            Collector: collector.raw(),
+            BroadPhaseLayerFilter: query.broad_phase_layer_filter.as_ref().into_jolt(),
+            ObjectLayerFilter: query.object_layer_filter.as_ref().into_jolt(),
+            BodyFilter: query.body_filter.as_ref().into_jolt(),
+            ShapeFilter: query.shape_filter.as_ref().into_jolt(),
+            ..unsafe { mem::zeroed() }
+        };
+
+        checked_call(|| unsafe {
+            JPC_NarrowPhaseQuery_CollideShape(self.raw, &mut raw_args);
+        });
+    }
+
+    /// Collides a shape with the world and returns every hit.
+    pub fn collide_shape_all(&self, query: CollideShapeQuery<'_>) -> Vec<CollideShapeHit> {
+        let mut collector = AllHitCollideShapeCollector::new();
+        self.collide_shape_with(query, &mut collector);
+        collector.result
+    }
+
+    /// Finds all shapes that contain `args.point`, reporting them to
+    /// `args.collector`.
+    pub fn collide_point(&self, args: CollidePointArgs<'_>) {
+        let mut raw_args = JPC_NarrowPhaseQuery_CollidePointArgs {
+            Point: args.point.into_jolt(),
+            Collector: args.collector.raw(),
+            BroadPhaseLayerFilter: args.broad_phase_layer_filter.as_ref().into_jolt(),
+            ObjectLayerFilter: args.object_layer_filter.as_ref().into_jolt(),
+            BodyFilter: args.body_filter.as_ref().into_jolt(),
+            ShapeFilter: args.shape_filter.as_ref().into_jolt(),
+        };
+
+        checked_call(|| unsafe {
+            JPC_NarrowPhaseQuery_CollidePoint(self.raw, &mut raw_args);
+        });
+    }
+
+    /// Collects all leaf shapes that overlap `args.bounds`, along with their
+    /// world transforms.
+    pub fn collect_transformed_shapes(
+        &self,
+        args: CollectTransformedShapesArgs<'_>,
+    ) -> Vec<TransformedShape> {
+        let mut collector = AllHitTransformedShapeCollector::new();
+        let collector_impl = TransformedShapeCollectorImpl::new_borrowed(&mut collector);
+
+        let mut raw_args = JPC_NarrowPhaseQuery_CollectTransformedShapesArgs {
+            Box: args.bounds.into_jolt(),
+            Collector: collector_impl.raw(),
+            BroadPhaseLayerFilter: args.broad_phase_layer_filter.as_ref().into_jolt(),
+            ObjectLayerFilter: args.object_layer_filter.as_ref().into_jolt(),
+            BodyFilter: args.body_filter.as_ref().into_jolt(),
+            ShapeFilter: args.shape_filter.as_ref().into_jolt(),
+        };
+
+        checked_call(|| unsafe {
+            JPC_NarrowPhaseQuery_CollectTransformedShapes(self.raw, &mut raw_args);
+        });
+
+        drop(collector_impl);
+        collector.result
     }
 
     pub fn raw(&self) -> *const JPC_NarrowPhaseQuery {
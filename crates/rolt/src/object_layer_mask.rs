@@ -0,0 +1,179 @@
+//! Jolt's mask-based object layers, where each object layer packs the groups
+//! an object belongs to and the groups it collides with, instead of being an
+//! index into a table.
+//!
+//! Requires the `object-layer-u32` feature.
+
+use joltc_sys::*;
+
+use crate::{
+    BroadPhaseLayer, BroadPhaseLayerInterface, ObjectLayer, ObjectLayerPairFilter,
+    ObjectVsBroadPhaseLayerFilter,
+};
+
+impl ObjectLayer {
+    /// Number of bits used for each of the group and the mask.
+    pub const GROUP_BITS: u32 = JPC_ObjectLayer::BITS / 2;
+
+    /// Packs the groups an object belongs to and the groups it collides with
+    /// into an object layer for [`ObjectLayerPairFilterMask`].
+    pub const fn from_group_and_mask(group: u16, mask: u16) -> Self {
+        Self::new(group as JPC_ObjectLayer | ((mask as JPC_ObjectLayer) << Self::GROUP_BITS))
+    }
+
+    /// Creates an object layer that belongs to `group` and collides with
+    /// every group.
+    pub const fn from_group(group: u16) -> Self {
+        Self::from_group_and_mask(group, u16::MAX)
+    }
+
+    /// The groups that an object in this layer belongs to.
+    pub const fn group(self) -> u16 {
+        self.raw() as u16
+    }
+
+    /// The groups that an object in this layer collides with.
+    pub const fn mask(self) -> u16 {
+        (self.raw() >> Self::GROUP_BITS) as u16
+    }
+}
+
+/// An [`ObjectLayerPairFilter`] for layers made with
+/// [`ObjectLayer::from_group_and_mask`]. Two layers collide if each one's
+/// group is in the other's mask.
+///
+/// See also: Jolt's [`ObjectLayerPairFilterMask`](https://jrouwe.github.io/JoltPhysicsDocs/5.1.0/class_object_layer_pair_filter_mask.html) class.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ObjectLayerPairFilterMask;
+
+impl ObjectLayerPairFilterMask {
+    pub const fn layers_collide(layer1: ObjectLayer, layer2: ObjectLayer) -> bool {
+        (layer1.group() & layer2.mask()) != 0 && (layer2.group() & layer1.mask()) != 0
+    }
+}
+
+impl ObjectLayerPairFilter for ObjectLayerPairFilterMask {
+    fn should_collide(&self, layer1: ObjectLayer, layer2: ObjectLayer) -> bool {
+        Self::layers_collide(layer1, layer2)
+    }
+}
+
+/// A [`BroadPhaseLayerInterface`] for layers made with
+/// [`ObjectLayer::from_group_and_mask`], which puts objects into broad phase
+/// layers by their group. The last broad phase layer is a fallback for
+/// objects that don't match any other layer, and collides with every object.
+///
+/// See also: Jolt's [`BroadPhaseLayerInterfaceMask`](https://jrouwe.github.io/JoltPhysicsDocs/5.1.0/class_broad_phase_layer_interface_mask.html) class.
+#[derive(Debug, Clone)]
+pub struct BroadPhaseLayerInterfaceMask {
+    mappings: Vec<GroupMapping>,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct GroupMapping {
+    groups_to_include: u16,
+    groups_to_exclude: u16,
+}
+
+impl BroadPhaseLayerInterfaceMask {
+    /// Creates an interface with `num_broad_phase_layers` layers, none of
+    /// which contain any groups until they are configured.
+    pub fn new(num_broad_phase_layers: u32) -> Self {
+        assert!(
+            num_broad_phase_layers > 0 && num_broad_phase_layers <= JPC_BroadPhaseLayer::MAX as u32,
+            "{num_broad_phase_layers} broad phase layers are not supported"
+        );
+
+        Self {
+            mappings: vec![GroupMapping::default(); num_broad_phase_layers as usize],
+        }
+    }
+
+    /// Puts objects that belong to any of `groups_to_include` and none of
+    /// `groups_to_exclude` into `layer`. If an object matches several broad
+    /// phase layers, the first one is used.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `layer` is not less than the number of broad phase layers.
+    pub fn configure_layer(
+        &mut self,
+        layer: BroadPhaseLayer,
+        groups_to_include: u16,
+        groups_to_exclude: u16,
+    ) {
+        let num_layers = self.mappings.len();
+        let mapping = self
+            .mappings
+            .get_mut(layer.raw() as usize)
+            .unwrap_or_else(|| {
+                panic!(
+                    "broad phase layer {} is out of bounds for {num_layers} layers",
+                    layer.raw()
+                )
+            });
+
+        *mapping = GroupMapping {
+            groups_to_include,
+            groups_to_exclude,
+        };
+    }
+
+    /// Returns the broad phase layer that objects in `layer` are put into.
+    /// Objects that don't match any broad phase layer are put into the last
+    /// one.
+    pub fn broad_phase_layer_for(&self, layer: ObjectLayer) -> BroadPhaseLayer {
+        let group = layer.group();
+        let index = self
+            .mappings
+            .iter()
+            .position(|mapping| {
+                (group & mapping.groups_to_include) != 0 && (group & mapping.groups_to_exclude) == 0
+            })
+            .unwrap_or(self.mappings.len() - 1);
+
+        BroadPhaseLayer::new(index as JPC_BroadPhaseLayer)
+    }
+
+    /// Creates the matching [`ObjectVsBroadPhaseLayerFilter`]. Changes made to
+    /// this interface afterwards aren't seen by the filter.
+    pub fn object_vs_broad_phase_layer_filter(&self) -> ObjectVsBroadPhaseLayerFilterMask {
+        ObjectVsBroadPhaseLayerFilterMask {
+            mappings: self.mappings.clone(),
+        }
+    }
+}
+
+impl BroadPhaseLayerInterface for BroadPhaseLayerInterfaceMask {
+    fn get_num_broad_phase_layers(&self) -> u32 {
+        self.mappings.len() as u32
+    }
+
+    fn get_broad_phase_layer(&self, layer: ObjectLayer) -> BroadPhaseLayer {
+        self.broad_phase_layer_for(layer)
+    }
+}
+
+/// An [`ObjectVsBroadPhaseLayerFilter`] for layers made with
+/// [`ObjectLayer::from_group_and_mask`], created by
+/// [`BroadPhaseLayerInterfaceMask::object_vs_broad_phase_layer_filter`]. An
+/// object collides with a broad phase layer if its mask includes any of the
+/// layer's groups, and always collides with the last, fallback layer.
+///
+/// See also: Jolt's [`ObjectVsBroadPhaseLayerFilterMask`](https://jrouwe.github.io/JoltPhysicsDocs/5.1.0/class_object_vs_broad_phase_layer_filter_mask.html) class.
+#[derive(Debug, Clone)]
+pub struct ObjectVsBroadPhaseLayerFilterMask {
+    mappings: Vec<GroupMapping>,
+}
+
+impl ObjectVsBroadPhaseLayerFilter for ObjectVsBroadPhaseLayerFilterMask {
+    fn should_collide(&self, layer1: ObjectLayer, layer2: BroadPhaseLayer) -> bool {
+        let index = layer2.raw() as usize;
+
+        index == self.mappings.len() - 1
+            || self
+                .mappings
+                .get(index)
+                .is_some_and(|mapping| (mapping.groups_to_include & layer1.mask()) != 0)
+    }
+}
@@ -0,0 +1,145 @@
+//! Keeps panics in user callbacks from unwinding into Jolt.
+//!
+//! Unwinding across the C++ boundary is undefined behavior, so every bridge
+//! in [`crate::traits`] and the assert handler run user code through
+//! [`catch_panic`]. The first panic is stored, and Jolt gets a default result
+//! in the meantime: filters reject everything, contact validation accepts the
+//! contact and collectors drop the hit.
+//!
+//! The stored panic is resumed when these return to Rust, which run Jolt
+//! through [`checked_call`] or [`checked_update`]:
+//!
+//! - building a physics system and `PhysicsSystem::update`,
+//! - the narrow phase queries,
+//! - creating shapes from settings,
+//! - `BodyInterface` calls that create, add, remove, destroy, move or reshape
+//!   bodies, or change their layer or group.
+//!
+//! Panics caught on the thread that made the call are stored per thread, so
+//! a query or shape creation resumes its own panics and never another
+//! thread's. Callbacks that run on Jolt's worker threads during an update
+//! store their panic in a slot shared by the process instead, which the next
+//! `PhysicsSystem::update` to return resumes.
+//!
+//! Other functions, like getters and in-place shape edits, don't check. A
+//! panic caught while they run, such as a failed assertion, stays pending
+//! until the next of the calls above on the same thread, or until the next
+//! update returns if one is running on another thread at the time. If several
+//! callbacks panic before a slot is emptied, only the first payload is kept.
+
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::panic::{catch_unwind, resume_unwind, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, PoisonError};
+
+type Payload = Box<dyn Any + Send>;
+
+thread_local! {
+    /// How many checked calls the current thread is inside of.
+    static CHECKED_CALLS: Cell<usize> = const { Cell::new(0) };
+
+    /// Panic caught on this thread that the next checked call resumes.
+    static PENDING_PANIC: RefCell<Option<Payload>> = const { RefCell::new(None) };
+}
+
+/// How many physics updates are running in the process.
+static RUNNING_UPDATES: AtomicUsize = AtomicUsize::new(0);
+
+/// Panic caught on a thread that isn't inside a checked call while an update
+/// was running, which is most likely one of Jolt's worker threads.
+static WORKER_PANIC: Mutex<Option<Payload>> = Mutex::new(None);
+
+/// Runs `f`, returning `default` to the caller if it panics.
+pub(crate) fn catch_panic<R>(default: R, f: impl FnOnce() -> R) -> R {
+    match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(value) => value,
+        Err(payload) => {
+            store_panic(payload);
+            default
+        }
+    }
+}
+
+/// Runs `f`, which calls into Jolt, then resumes any panic that callbacks on
+/// this thread caught in the meantime. `f`'s result is dropped while
+/// unwinding, so anything it returns is cleaned up properly.
+pub(crate) fn checked_call<R>(f: impl FnOnce() -> R) -> R {
+    let value = {
+        let _call = Counter::enter_checked_call();
+        f()
+    };
+
+    if let Some(payload) = PENDING_PANIC.with(|pending| pending.borrow_mut().take()) {
+        resume_unwind(payload);
+    }
+
+    value
+}
+
+/// Like [`checked_call`], but also resumes panics caught on Jolt's worker
+/// threads while `f` runs a physics update.
+pub(crate) fn checked_update<R>(f: impl FnOnce() -> R) -> R {
+    let value = {
+        let _update = Counter::enter_update();
+        checked_call(f)
+    };
+
+    let payload = WORKER_PANIC
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .take();
+
+    if let Some(payload) = payload {
+        resume_unwind(payload);
+    }
+
+    value
+}
+
+fn store_panic(payload: Payload) {
+    let in_checked_call = CHECKED_CALLS.with(Cell::get) > 0;
+
+    // Jobs are handed to the worker threads through Jolt's own
+    // synchronization, so they always see the update that started them.
+    if in_checked_call || RUNNING_UPDATES.load(Ordering::Relaxed) == 0 {
+        PENDING_PANIC.with(|pending| {
+            pending.borrow_mut().get_or_insert(payload);
+        });
+    } else {
+        WORKER_PANIC
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get_or_insert(payload);
+    }
+}
+
+/// Decrements a counter when dropped, even if the call that incremented it
+/// unwinds.
+enum Counter {
+    CheckedCall,
+    Update,
+}
+
+impl Counter {
+    fn enter_checked_call() -> Self {
+        CHECKED_CALLS.with(|calls| calls.set(calls.get() + 1));
+        Self::CheckedCall
+    }
+
+    fn enter_update() -> Self {
+        RUNNING_UPDATES.fetch_add(1, Ordering::Relaxed);
+        Self::Update
+    }
+}
+
+impl Drop for Counter {
+    fn drop(&mut self) {
+        match self {
+            Self::CheckedCall => CHECKED_CALLS.with(|calls| calls.set(calls.get() - 1)),
+            Self::Update => {
+                RUNNING_UPDATES.fetch_sub(1, Ordering::Relaxed);
+            }
+        }
+    }
+}
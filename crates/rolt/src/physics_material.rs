@@ -0,0 +1,223 @@
+use std::ffi::CString;
+use std::fmt;
+use std::ptr;
+use std::sync::Arc;
+
+use joltc_sys::*;
+
+use crate::{Color, IntoJolt, RefConst, Shape, SubShapeId};
+
+/// A surface material that can be assigned to shapes, carrying arbitrary data
+/// of type `T` for game logic like footstep sounds.
+///
+/// Jolt only cares about the debug name and color. Friction and restitution
+/// are configured on the body, or per contact in a
+/// [`ContactListener`][crate::ContactListener].
+///
+/// See also: Jolt's [`PhysicsMaterial`](https://jrouwe.github.io/JoltPhysicsDocs/5.1.0/class_physics_material.html) class.
+pub struct PhysicsMaterial<T> {
+    raw: RefConst<JPC_PhysicsMaterial>,
+    debug_name: String,
+    debug_color: Color,
+    data: T,
+}
+
+impl<T> PhysicsMaterial<T> {
+    pub fn new(debug_name: impl Into<String>, debug_color: Color, data: T) -> Self {
+        let debug_name = debug_name.into();
+
+        // Interior nul bytes would cut the name short on the Jolt side, which
+        // is fine for a name that is only used for debugging.
+        let c_name = CString::new(debug_name.replace('\0', "")).unwrap();
+
+        let raw = unsafe {
+            let raw = JPC_PhysicsMaterialSimple_new(c_name.as_ptr(), debug_color.into_jolt());
+            RefConst::from_active(raw)
+        };
+
+        Self {
+            raw,
+            debug_name,
+            debug_color,
+            data,
+        }
+    }
+
+    pub fn debug_name(&self) -> &str {
+        &self.debug_name
+    }
+
+    pub fn debug_color(&self) -> Color {
+        self.debug_color
+    }
+
+    pub fn data(&self) -> &T {
+        &self.data
+    }
+
+    /// Returns a handle to this material for the `material` field of shape
+    /// settings, like
+    /// [`ConvexHullShapeSettings`][crate::ConvexHullShapeSettings].
+    pub fn to_ref(&self) -> PhysicsMaterialRef {
+        PhysicsMaterialRef {
+            raw: self.raw.clone(),
+        }
+    }
+
+    /// Returns the material pointer to put into the `Material` field of shape
+    /// settings, like [`JPC_BoxShapeSettings`].
+    pub fn raw(&self) -> *const JPC_PhysicsMaterial {
+        self.raw.get()
+    }
+}
+
+/// A reference-counted handle to a material that Jolt returned, like from
+/// [`Shape::material`]. Jolt only knows the debug name and color, so use
+/// [`PhysicsMaterialList::find`] to get back to the [`PhysicsMaterial`] and
+/// its data.
+#[derive(Clone)]
+pub struct PhysicsMaterialRef {
+    raw: RefConst<JPC_PhysicsMaterial>,
+}
+
+impl PhysicsMaterialRef {
+    /// Wraps a material pointer and starts reference counting it, or returns
+    /// `None` if it's null.
+    ///
+    /// # Safety
+    ///
+    /// `raw` must be null or point to a live material.
+    pub unsafe fn from_raw(raw: *const JPC_PhysicsMaterial) -> Option<Self> {
+        if raw.is_null() {
+            None
+        } else {
+            Some(Self {
+                raw: RefConst::from_active(raw),
+            })
+        }
+    }
+
+    pub fn raw(&self) -> *const JPC_PhysicsMaterial {
+        self.raw.get()
+    }
+}
+
+impl PartialEq for PhysicsMaterialRef {
+    fn eq(&self, other: &Self) -> bool {
+        ptr::eq(self.raw(), other.raw())
+    }
+}
+
+impl Eq for PhysicsMaterialRef {}
+
+impl fmt::Debug for PhysicsMaterialRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("PhysicsMaterialRef")
+            .field(&self.raw())
+            .finish()
+    }
+}
+
+/// An ordered list of materials, used to assign materials to the triangles of
+/// a mesh and to look up which material was hit.
+///
+/// Mesh triangles refer to materials by their index in this list through
+/// `JPC_IndexedTriangle::materialIndex`.
+pub struct PhysicsMaterialList<T> {
+    materials: Vec<Arc<PhysicsMaterial<T>>>,
+}
+
+impl<T> Default for PhysicsMaterialList<T> {
+    fn default() -> Self {
+        Self {
+            materials: Vec::new(),
+        }
+    }
+}
+
+impl<T> PhysicsMaterialList<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a material to the list and returns its index.
+    pub fn push(&mut self, material: impl Into<Arc<PhysicsMaterial<T>>>) -> u32 {
+        let index = self.materials.len() as u32;
+        self.materials.push(material.into());
+        index
+    }
+
+    pub fn get(&self, index: u32) -> Option<&Arc<PhysicsMaterial<T>>> {
+        self.materials.get(index as usize)
+    }
+
+    pub fn len(&self) -> usize {
+        self.materials.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.materials.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Arc<PhysicsMaterial<T>>> {
+        self.materials.iter()
+    }
+
+    /// Returns the index of the first material with the given debug name.
+    pub fn index_of(&self, debug_name: &str) -> Option<u32> {
+        self.materials
+            .iter()
+            .position(|material| material.debug_name == debug_name)
+            .map(|index| index as u32)
+    }
+
+    /// Returns the material pointers to put into the `Materials` field of
+    /// [`JPC_MeshShapeSettings`]. The list must outlive the settings.
+    pub fn raw_materials(&self) -> Vec<*const JPC_PhysicsMaterial> {
+        self.materials
+            .iter()
+            .map(|material| material.raw())
+            .collect()
+    }
+
+    /// Finds the material in this list that `material` refers to.
+    pub fn find(&self, material: &PhysicsMaterialRef) -> Option<&Arc<PhysicsMaterial<T>>> {
+        self.find_raw(material.raw())
+    }
+
+    /// Finds the material of the sub-shape of `shape` identified by `id`, like
+    /// the `sub_shape_id_2` of a [`ShapeCastHit`][crate::ShapeCastHit].
+    pub fn find_for_shape(
+        &self,
+        shape: &Shape,
+        id: SubShapeId,
+    ) -> Option<&Arc<PhysicsMaterial<T>>> {
+        self.find(&shape.material(id)?)
+    }
+
+    /// Finds the material of the sub-shape of `body` identified by `id`. This
+    /// is useful in a [`ContactListener`][crate::ContactListener], where the
+    /// sub-shape IDs come from the contact manifold.
+    pub fn find_for_body(
+        &self,
+        body: &JPC_Body,
+        id: SubShapeId,
+    ) -> Option<&Arc<PhysicsMaterial<T>>> {
+        let raw = unsafe {
+            let shape = JPC_Body_GetShape(body);
+            JPC_Shape_GetMaterial(shape, id.raw())
+        };
+
+        self.find_raw(raw)
+    }
+
+    fn find_raw(&self, raw: *const JPC_PhysicsMaterial) -> Option<&Arc<PhysicsMaterial<T>>> {
+        if raw.is_null() {
+            return None;
+        }
+
+        self.materials
+            .iter()
+            .find(|material| ptr::eq(material.raw(), raw))
+    }
+}
@@ -0,0 +1,121 @@
+use joltc_sys::*;
+
+use crate::{FromJolt, IntoJolt};
+
+/// Solver and sleeping settings for a [`PhysicsSystem`][crate::PhysicsSystem].
+///
+/// See also: Jolt's [`PhysicsSettings`](https://jrouwe.github.io/JoltPhysicsDocs/5.1.0/struct_physics_settings.html) struct.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PhysicsSettings {
+    /// Number of solver iterations for velocities. More iterations make
+    /// stacks and joints stiffer but cost more.
+    pub num_velocity_steps: u32,
+
+    /// Number of solver iterations for positions.
+    pub num_position_steps: u32,
+
+    /// Fraction of the position error that is corrected each step, between 0
+    /// and 1.
+    pub baumgarte: f32,
+
+    /// Bodies closer than this distance start generating contacts before
+    /// they touch, in meters.
+    pub speculative_contact_distance: f32,
+
+    /// How far bodies are allowed to sink into each other before the
+    /// position solver pushes them apart, in meters.
+    pub penetration_slop: f32,
+
+    /// Fraction of its inner radius that a body must move in one step before
+    /// it is cast instead of moved, when it uses continuous collision
+    /// detection.
+    pub linear_cast_threshold: f32,
+
+    /// Fraction of its inner radius that a cast body may sink into another
+    /// body.
+    pub linear_cast_max_penetration: f32,
+
+    /// Bodies that hit each other slower than this don't bounce, in meters
+    /// per second.
+    pub min_velocity_for_restitution: f32,
+
+    pub allow_sleeping: bool,
+
+    /// How long a body must stay below the sleep threshold before it goes to
+    /// sleep, in seconds.
+    pub time_before_sleep: f32,
+
+    /// Bodies whose points all move slower than this can go to sleep, in
+    /// meters per second.
+    pub point_velocity_sleep_threshold: f32,
+
+    /// Makes the simulation give the same results when run again with the
+    /// same inputs, at a small cost.
+    pub deterministic_simulation: bool,
+
+    /// Starts the solver from the previous step's impulses.
+    pub constraint_warm_start: bool,
+    pub use_body_pair_contact_cache: bool,
+    pub use_manifold_reduction: bool,
+    pub use_large_island_splitter: bool,
+    pub check_active_edges: bool,
+}
+
+impl Default for PhysicsSettings {
+    fn default() -> Self {
+        Self::from_jolt(JPC_PhysicsSettings::default())
+    }
+}
+
+impl IntoJolt for PhysicsSettings {
+    type Jolt = JPC_PhysicsSettings;
+
+    fn into_jolt(self) -> Self::Jolt {
+        JPC_PhysicsSettings {
+            NumVelocitySteps: self.num_velocity_steps,
+            NumPositionSteps: self.num_position_steps,
+            Baumgarte: self.baumgarte,
+            SpeculativeContactDistance: self.speculative_contact_distance,
+            PenetrationSlop: self.penetration_slop,
+            LinearCastThreshold: self.linear_cast_threshold,
+            LinearCastMaxPenetration: self.linear_cast_max_penetration,
+            MinVelocityForRestitution: self.min_velocity_for_restitution,
+            AllowSleeping: self.allow_sleeping,
+            TimeBeforeSleep: self.time_before_sleep,
+            PointVelocitySleepThreshold: self.point_velocity_sleep_threshold,
+            DeterministicSimulation: self.deterministic_simulation,
+            ConstraintWarmStart: self.constraint_warm_start,
+            UseBodyPairContactCache: self.use_body_pair_contact_cache,
+            UseManifoldReduction: self.use_manifold_reduction,
+            UseLargeIslandSplitter: self.use_large_island_splitter,
+            CheckActiveEdges: self.check_active_edges,
+            ..Default::default()
+        }
+    }
+}
+
+impl FromJolt for PhysicsSettings {
+    type Jolt = JPC_PhysicsSettings;
+
+    fn from_jolt(value: Self::Jolt) -> Self {
+        Self {
+            num_velocity_steps: value.NumVelocitySteps,
+            num_position_steps: value.NumPositionSteps,
+            baumgarte: value.Baumgarte,
+            speculative_contact_distance: value.SpeculativeContactDistance,
+            penetration_slop: value.PenetrationSlop,
+            linear_cast_threshold: value.LinearCastThreshold,
+            linear_cast_max_penetration: value.LinearCastMaxPenetration,
+            min_velocity_for_restitution: value.MinVelocityForRestitution,
+            allow_sleeping: value.AllowSleeping,
+            time_before_sleep: value.TimeBeforeSleep,
+            point_velocity_sleep_threshold: value.PointVelocitySleepThreshold,
+            deterministic_simulation: value.DeterministicSimulation,
+            constraint_warm_start: value.ConstraintWarmStart,
+            use_body_pair_contact_cache: value.UseBodyPairContactCache,
+            use_manifold_reduction: value.UseManifoldReduction,
+            use_large_island_splitter: value.UseLargeIslandSplitter,
+            check_active_edges: value.CheckActiveEdges,
+        }
+    }
+}
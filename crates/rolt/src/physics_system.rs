@@ -1,78 +1,92 @@
+use std::fmt;
 use std::ptr;
 
+use bitflags::bitflags;
+
 use joltc_sys::*;
 
+use crate::panic_guard::{checked_call, checked_update};
 use crate::{
-    BodyInterface, BroadPhaseLayerInterfaceImpl, ContactListenerImpl, NarrowPhaseQuery,
-    ObjectLayerPairFilterImpl, ObjectVsBroadPhaseLayerFilterImpl, SimShapeFilterImpl,
+    BodyInterface, BroadPhaseLayerInterfaceImpl, ContactListenerImpl, IntoJolt, IntoRolt,
+    NarrowPhaseQuery, ObjectLayerPairFilterImpl, ObjectVsBroadPhaseLayerFilterImpl,
+    PhysicsSettings, PhysicsSystemBuilder, PhysicsSystemCapacities, SimShapeFilterImpl, Vec3,
 };
 
 /// The root of everything for a physics simulation.
 ///
+/// `'a` is how long the listeners and layer interfaces live. Ones created
+/// with `new_borrowed`, like [`ContactListenerImpl::new_borrowed`], can
+/// borrow game state, and the borrow checker makes sure they outlive the
+/// system. Owned ones are `'static`.
+///
+/// # Threads
+///
+/// The system is `Send` and `Sync`. Jolt's [`BodyInterface`] and
+/// [`NarrowPhaseQuery`] lock the bodies they touch, so both can be used from
+/// several threads at once, for example to run queries in parallel. Changing
+/// the system's configuration, [`Self::optimize_broad_phase`] and
+/// [`Self::update`] take `&mut self`, so nothing else can use the system
+/// while they run.
+///
+/// The listeners and layer interfaces are called from Jolt's worker threads
+/// during [`Self::update`], which is why their traits require `Send` and
+/// `Sync`.
+///
 /// See also: Jolt's [`PhysicsSystem`](https://jrouwe.github.io/JoltPhysicsDocs/5.1.0/class_physics_system.html) class.
-pub struct PhysicsSystem {
+pub struct PhysicsSystem<'a> {
     raw: *mut JPC_PhysicsSystem,
-    broad_phase_layer_interface: Option<BroadPhaseLayerInterfaceImpl<'static>>,
-    object_vs_broad_phase_layer_filter: Option<ObjectVsBroadPhaseLayerFilterImpl<'static>>,
-    object_layer_pair_filter: Option<ObjectLayerPairFilterImpl<'static>>,
-    sim_shape_filter: Option<SimShapeFilterImpl<'static>>,
-    contact_listener: Option<ContactListenerImpl<'static>>,
+
+    // Jolt keeps pointers to the layer interfaces, so they are dropped after
+    // the system is deleted.
+    _broad_phase_layer_interface: BroadPhaseLayerInterfaceImpl<'a>,
+    _object_vs_broad_phase_layer_filter: ObjectVsBroadPhaseLayerFilterImpl<'a>,
+    _object_layer_pair_filter: ObjectLayerPairFilterImpl<'a>,
+    sim_shape_filter: Option<SimShapeFilterImpl<'a>>,
+    contact_listener: Option<ContactListenerImpl<'a>>,
 }
 
-impl PhysicsSystem {
-    pub fn new() -> Self {
-        unsafe {
-            Self {
-                raw: JPC_PhysicsSystem_new(),
-                broad_phase_layer_interface: None,
-                object_vs_broad_phase_layer_filter: None,
-                object_layer_pair_filter: None,
-                sim_shape_filter: None,
-                contact_listener: None,
-            }
-        }
+impl<'a> PhysicsSystem<'a> {
+    /// Starts configuring a new physics system.
+    pub fn builder() -> PhysicsSystemBuilder<'a> {
+        PhysicsSystemBuilder::new()
     }
 
-    pub fn init(
-        &mut self,
-        max_bodies: u32,
-        num_body_mutexes: u32,
-        max_body_pairs: u32,
-        max_contact_constraints: u32,
-        broad_phase_layer_interface: impl Into<BroadPhaseLayerInterfaceImpl<'static>>,
-        object_vs_broad_phase_layer_filter: impl Into<ObjectVsBroadPhaseLayerFilterImpl<'static>>,
-        object_layer_pair_filter: impl Into<ObjectLayerPairFilterImpl<'static>>,
-    ) {
-        let bpli = broad_phase_layer_interface.into();
-        let bpli_raw = bpli.raw();
-        self.broad_phase_layer_interface = Some(bpli);
+    /// Creates and initializes the system. The capacities must already have
+    /// been validated by [`PhysicsSystemBuilder`].
+    pub(crate) fn init(
+        capacities: &PhysicsSystemCapacities,
+        broad_phase_layer_interface: BroadPhaseLayerInterfaceImpl<'a>,
+        object_vs_broad_phase_layer_filter: ObjectVsBroadPhaseLayerFilterImpl<'a>,
+        object_layer_pair_filter: ObjectLayerPairFilterImpl<'a>,
+    ) -> Self {
+        // The system is assembled before any panic is resumed, so that it is
+        // cleaned up properly while unwinding.
+        checked_call(|| unsafe {
+            let raw = JPC_PhysicsSystem_new();
 
-        let ovbplf = object_vs_broad_phase_layer_filter.into();
-        let ovbplf_raw = ovbplf.raw();
-        self.object_vs_broad_phase_layer_filter = Some(ovbplf);
-
-        let olpf = object_layer_pair_filter.into();
-        let olpf_raw = olpf.raw();
-        self.object_layer_pair_filter = Some(olpf);
-
-        unsafe {
             JPC_PhysicsSystem_Init(
-                self.raw,
-                max_bodies,
-                num_body_mutexes,
-                max_body_pairs,
-                max_contact_constraints,
-                bpli_raw,
-                ovbplf_raw,
-                olpf_raw,
+                raw,
+                capacities.max_bodies,
+                capacities.num_body_mutexes,
+                capacities.max_body_pairs,
+                capacities.max_contact_constraints,
+                broad_phase_layer_interface.raw(),
+                object_vs_broad_phase_layer_filter.raw(),
+                object_layer_pair_filter.raw(),
             );
-        }
+
+            Self {
+                raw,
+                _broad_phase_layer_interface: broad_phase_layer_interface,
+                _object_vs_broad_phase_layer_filter: object_vs_broad_phase_layer_filter,
+                _object_layer_pair_filter: object_layer_pair_filter,
+                sim_shape_filter: None,
+                contact_listener: None,
+            }
+        })
     }
 
-    pub fn set_sim_shape_filter(
-        &mut self,
-        sim_shape_filter: impl Into<SimShapeFilterImpl<'static>>,
-    ) {
+    pub fn set_sim_shape_filter(&mut self, sim_shape_filter: impl Into<SimShapeFilterImpl<'a>>) {
         let sim_shape_filter = sim_shape_filter.into();
         let raw = sim_shape_filter.raw();
         self.sim_shape_filter = Some(sim_shape_filter);
@@ -84,7 +98,7 @@ impl PhysicsSystem {
 
     pub fn set_contact_listener(
         &mut self,
-        contact_listener: Option<impl Into<ContactListenerImpl<'static>>>,
+        contact_listener: Option<impl Into<ContactListenerImpl<'a>>>,
     ) {
         if let Some(contact_listener) = contact_listener {
             let contact_listener = contact_listener.into();
@@ -98,34 +112,83 @@ impl PhysicsSystem {
             unsafe {
                 JPC_PhysicsSystem_SetContactListener(self.raw, ptr::null_mut());
             }
+
+            self.contact_listener = None;
         }
     }
 
-    pub fn optimize_broad_phase(&self) {
+    pub fn gravity(&self) -> Vec3 {
+        unsafe { JPC_PhysicsSystem_GetGravity(self.raw).into_rolt() }
+    }
+
+    pub fn set_gravity(&mut self, gravity: Vec3) {
+        unsafe {
+            JPC_PhysicsSystem_SetGravity(self.raw, gravity.into_jolt());
+        }
+    }
+
+    pub fn physics_settings(&self) -> PhysicsSettings {
+        unsafe { JPC_PhysicsSystem_GetPhysicsSettings(self.raw).into_rolt() }
+    }
+
+    /// Replaces the solver and sleeping settings. They apply from the next
+    /// call to [`Self::update`].
+    pub fn set_physics_settings(&mut self, settings: PhysicsSettings) {
+        let settings = settings.into_jolt();
+
+        unsafe {
+            JPC_PhysicsSystem_SetPhysicsSettings(self.raw, &settings);
+        }
+    }
+
+    /// Rebuilds the broad phase's trees so that queries are fast, for example
+    /// after adding many bodies at once. Jolt doesn't lock the broad phase
+    /// while doing this, so this takes `&mut self`.
+    pub fn optimize_broad_phase(&mut self) {
         unsafe {
             JPC_PhysicsSystem_OptimizeBroadPhase(self.raw);
         }
     }
 
+    /// Steps the simulation forward by `delta_time`.
+    ///
+    /// Returns an error if one of the buffers sized by
+    /// [`PhysicsSystemCapacities`] ran out
+    /// of space during the step. The step still completes, but some contacts
+    /// were dropped, so bodies may pass through each other.
+    ///
+    /// # Panics
+    ///
+    /// If a listener or filter panics during the step, Jolt is given a safe
+    /// default in its place and the panic is resumed here once the step is
+    /// done. Panics from callbacks that Jolt ran at other times, like the
+    /// layer interfaces while adding a body, are also resumed here.
+    ///
     /// # Safety
     ///
     /// `temp_allocator` and `job_system` must both be valid and live for the
     /// duration of this function.
     pub unsafe fn update(
-        &self,
+        &mut self,
         delta_time: f32,
         collision_steps: i32,
         temp_allocator: *mut JPC_TempAllocatorImpl,
         job_system: *mut JPC_JobSystemThreadPool,
-    ) {
-        unsafe {
+    ) -> Result<(), PhysicsUpdateError> {
+        let errors = checked_update(|| unsafe {
             JPC_PhysicsSystem_Update(
                 self.raw,
                 delta_time,
                 collision_steps,
                 temp_allocator,
                 job_system.cast::<JPC_JobSystem>(),
-            );
+            )
+        });
+
+        if errors == JPC_PHYSICS_UPDATE_ERROR_NONE {
+            Ok(())
+        } else {
+            Err(PhysicsUpdateError::from_bits_retain(errors))
         }
     }
 
@@ -176,10 +239,66 @@ impl PhysicsSystem {
     }
 }
 
-impl Drop for PhysicsSystem {
+// SAFETY: Jolt's physics system can be used from any thread. The listeners
+// and layer interfaces it holds are `Send` and `Sync`. Methods that don't
+// lock, which are `update`, `optimize_broad_phase` and the configuration
+// setters, take `&mut self`. The body interface, narrow phase queries,
+// `draw_bodies` and the constraint methods lock what they use.
+unsafe impl Send for PhysicsSystem<'_> {}
+unsafe impl Sync for PhysicsSystem<'_> {}
+
+impl Drop for PhysicsSystem<'_> {
     fn drop(&mut self) {
         unsafe {
             JPC_PhysicsSystem_delete(self.raw);
         }
     }
 }
+
+bitflags! {
+    /// The buffers that overflowed during [`PhysicsSystem::update`].
+    ///
+    /// See also: Jolt's [`EPhysicsUpdateError`](https://jrouwe.github.io/JoltPhysicsDocs/5.1.0/_e_physics_update_error_8h.html) enum.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct PhysicsUpdateError: u32 {
+        /// The manifold cache is full. Increase `max_contact_constraints`.
+        const MANIFOLD_CACHE_FULL = JPC_PHYSICS_UPDATE_ERROR_MANIFOLD_CACHE_FULL;
+
+        /// The body pair cache is full. Increase `max_body_pairs`.
+        const BODY_PAIR_CACHE_FULL = JPC_PHYSICS_UPDATE_ERROR_BODY_PAIR_CACHE_FULL;
+
+        /// The contact constraints buffer is full. Increase
+        /// `max_contact_constraints`.
+        const CONTACT_CONSTRAINTS_FULL = JPC_PHYSICS_UPDATE_ERROR_CONTACT_CONSTRAINTS_FULL;
+    }
+}
+
+impl fmt::Display for PhysicsUpdateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut messages = Vec::new();
+
+        if self.contains(Self::MANIFOLD_CACHE_FULL) {
+            messages.push("manifold cache is full");
+        }
+
+        if self.contains(Self::BODY_PAIR_CACHE_FULL) {
+            messages.push("body pair cache is full");
+        }
+
+        if self.contains(Self::CONTACT_CONSTRAINTS_FULL) {
+            messages.push("contact constraints buffer is full");
+        }
+
+        if messages.is_empty() {
+            write!(f, "physics update failed with error {:#x}", self.bits())
+        } else {
+            write!(
+                f,
+                "physics update dropped contacts: {}",
+                messages.join(", ")
+            )
+        }
+    }
+}
+
+impl std::error::Error for PhysicsUpdateError {}
@@ -0,0 +1,283 @@
+use std::fmt;
+
+use crate::{
+    validate_layers, BroadPhaseLayerInterface, BroadPhaseLayerInterfaceImpl, CollisionLayers,
+    ContactListenerImpl, LayerValidationError, ObjectLayerPairFilter, ObjectLayerPairFilterImpl,
+    ObjectVsBroadPhaseLayerFilter, ObjectVsBroadPhaseLayerFilterImpl, PhysicsSettings,
+    PhysicsSystem, SimShapeFilterImpl, Vec3,
+};
+
+/// How many bodies, body pairs and contacts a [`PhysicsSystem`] has room for.
+/// These can't be changed after the system is built.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PhysicsSystemCapacities {
+    /// Maximum number of bodies that can exist at once.
+    pub max_bodies: u32,
+
+    /// Number of mutexes that protect the bodies, which must be a power of
+    /// two. Zero picks a default.
+    pub num_body_mutexes: u32,
+
+    /// Maximum number of pairs of bodies whose bounds overlap that are
+    /// processed in one step. Pairs beyond this are dropped.
+    pub max_body_pairs: u32,
+
+    /// Maximum number of contact constraints that are processed in one step.
+    /// Contacts beyond this are dropped.
+    pub max_contact_constraints: u32,
+}
+
+impl PhysicsSystemCapacities {
+    /// The largest number of bodies Jolt supports, limited by the bits in a
+    /// body ID that are used for the index.
+    pub const MAX_BODIES_LIMIT: u32 = 0x7f_ffff;
+
+    fn validate(&self) -> Result<(), PhysicsSystemError> {
+        if self.max_bodies == 0 || self.max_bodies > Self::MAX_BODIES_LIMIT {
+            return Err(PhysicsSystemError::InvalidMaxBodies(self.max_bodies));
+        }
+
+        if self.num_body_mutexes != 0 && !self.num_body_mutexes.is_power_of_two() {
+            return Err(PhysicsSystemError::InvalidNumBodyMutexes(
+                self.num_body_mutexes,
+            ));
+        }
+
+        if self.max_body_pairs == 0 {
+            return Err(PhysicsSystemError::ZeroCapacity("max_body_pairs"));
+        }
+
+        if self.max_contact_constraints == 0 {
+            return Err(PhysicsSystemError::ZeroCapacity("max_contact_constraints"));
+        }
+
+        Ok(())
+    }
+}
+
+/// The capacities used by Jolt's samples, which fit most games.
+impl Default for PhysicsSystemCapacities {
+    fn default() -> Self {
+        Self {
+            max_bodies: 65536,
+            num_body_mutexes: 0,
+            max_body_pairs: 65536,
+            max_contact_constraints: 10240,
+        }
+    }
+}
+
+/// Configures and creates a [`PhysicsSystem`], from
+/// [`PhysicsSystem::builder`].
+///
+/// The layer interfaces are required, either from [`Self::layers`] or
+/// [`Self::collision_layers`]. Everything else has a default.
+pub struct PhysicsSystemBuilder<'a> {
+    capacities: PhysicsSystemCapacities,
+    layers: Option<(
+        BroadPhaseLayerInterfaceImpl<'a>,
+        ObjectVsBroadPhaseLayerFilterImpl<'a>,
+        ObjectLayerPairFilterImpl<'a>,
+    )>,
+    layer_error: Option<LayerValidationError>,
+    contact_listener: Option<ContactListenerImpl<'a>>,
+    sim_shape_filter: Option<SimShapeFilterImpl<'a>>,
+    physics_settings: Option<PhysicsSettings>,
+    gravity: Option<Vec3>,
+}
+
+impl<'a> PhysicsSystemBuilder<'a> {
+    pub(crate) fn new() -> Self {
+        Self {
+            capacities: PhysicsSystemCapacities::default(),
+            layers: None,
+            layer_error: None,
+            contact_listener: None,
+            sim_shape_filter: None,
+            physics_settings: None,
+            gravity: None,
+        }
+    }
+
+    pub fn capacities(mut self, capacities: PhysicsSystemCapacities) -> Self {
+        self.capacities = capacities;
+        self
+    }
+
+    pub fn max_bodies(mut self, max_bodies: u32) -> Self {
+        self.capacities.max_bodies = max_bodies;
+        self
+    }
+
+    pub fn num_body_mutexes(mut self, num_body_mutexes: u32) -> Self {
+        self.capacities.num_body_mutexes = num_body_mutexes;
+        self
+    }
+
+    pub fn max_body_pairs(mut self, max_body_pairs: u32) -> Self {
+        self.capacities.max_body_pairs = max_body_pairs;
+        self
+    }
+
+    pub fn max_contact_constraints(mut self, max_contact_constraints: u32) -> Self {
+        self.capacities.max_contact_constraints = max_contact_constraints;
+        self
+    }
+
+    /// Sets the interfaces that decide which layers collide.
+    pub fn layers(
+        mut self,
+        broad_phase_layer_interface: impl Into<BroadPhaseLayerInterfaceImpl<'a>>,
+        object_vs_broad_phase_layer_filter: impl Into<ObjectVsBroadPhaseLayerFilterImpl<'a>>,
+        object_layer_pair_filter: impl Into<ObjectLayerPairFilterImpl<'a>>,
+    ) -> Self {
+        self.layer_error = None;
+        self.layers = Some((
+            broad_phase_layer_interface.into(),
+            object_vs_broad_phase_layer_filter.into(),
+            object_layer_pair_filter.into(),
+        ));
+        self
+    }
+
+    /// Like [`Self::layers`], but first checks the interfaces with
+    /// [`validate_layers`] for the object layers below `num_object_layers`.
+    /// If they are inconsistent, [`Self::build`] returns
+    /// [`PhysicsSystemError::InvalidLayers`] instead of letting Jolt assert.
+    pub fn validated_layers<B, O, P>(
+        mut self,
+        num_object_layers: u32,
+        broad_phase_layer_interface: B,
+        object_vs_broad_phase_layer_filter: O,
+        object_layer_pair_filter: P,
+    ) -> Self
+    where
+        B: BroadPhaseLayerInterface + 'a,
+        O: ObjectVsBroadPhaseLayerFilter + 'a,
+        P: ObjectLayerPairFilter + 'a,
+    {
+        let layer_error = validate_layers(
+            num_object_layers,
+            &broad_phase_layer_interface,
+            &object_vs_broad_phase_layer_filter,
+            &object_layer_pair_filter,
+        )
+        .err();
+
+        self = self.layers(
+            broad_phase_layer_interface,
+            object_vs_broad_phase_layer_filter,
+            object_layer_pair_filter,
+        );
+        self.layer_error = layer_error;
+        self
+    }
+
+    /// Uses `layers` for all of the layer interfaces.
+    pub fn collision_layers(self, layers: &CollisionLayers) -> Self {
+        self.layers(layers.clone(), layers.clone(), layers.clone())
+    }
+
+    pub fn contact_listener(
+        mut self,
+        contact_listener: impl Into<ContactListenerImpl<'a>>,
+    ) -> Self {
+        self.contact_listener = Some(contact_listener.into());
+        self
+    }
+
+    pub fn sim_shape_filter(mut self, sim_shape_filter: impl Into<SimShapeFilterImpl<'a>>) -> Self {
+        self.sim_shape_filter = Some(sim_shape_filter.into());
+        self
+    }
+
+    pub fn physics_settings(mut self, physics_settings: PhysicsSettings) -> Self {
+        self.physics_settings = Some(physics_settings);
+        self
+    }
+
+    pub fn gravity(mut self, gravity: Vec3) -> Self {
+        self.gravity = Some(gravity);
+        self
+    }
+
+    /// Checks the configuration, then creates and initializes the system.
+    pub fn build(self) -> Result<PhysicsSystem<'a>, PhysicsSystemError> {
+        self.capacities.validate()?;
+
+        let (bpli, ovbplf, olpf) = self.layers.ok_or(PhysicsSystemError::MissingLayers)?;
+
+        if let Some(err) = self.layer_error {
+            return Err(PhysicsSystemError::InvalidLayers(err));
+        }
+
+        let mut system = PhysicsSystem::init(&self.capacities, bpli, ovbplf, olpf);
+
+        if let Some(sim_shape_filter) = self.sim_shape_filter {
+            system.set_sim_shape_filter(sim_shape_filter);
+        }
+
+        if let Some(contact_listener) = self.contact_listener {
+            system.set_contact_listener(Some(contact_listener));
+        }
+
+        if let Some(physics_settings) = self.physics_settings {
+            system.set_physics_settings(physics_settings);
+        }
+
+        if let Some(gravity) = self.gravity {
+            system.set_gravity(gravity);
+        }
+
+        Ok(system)
+    }
+}
+
+/// An error returned by [`PhysicsSystemBuilder::build`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PhysicsSystemError {
+    /// No layer interfaces were given.
+    MissingLayers,
+
+    /// `max_bodies` is zero or larger than
+    /// [`PhysicsSystemCapacities::MAX_BODIES_LIMIT`].
+    InvalidMaxBodies(u32),
+
+    /// `num_body_mutexes` is not zero or a power of two.
+    InvalidNumBodyMutexes(u32),
+
+    /// The named capacity is zero.
+    ZeroCapacity(&'static str),
+
+    /// The layer interfaces given to [`PhysicsSystemBuilder::validated_layers`]
+    /// are inconsistent.
+    InvalidLayers(LayerValidationError),
+}
+
+impl fmt::Display for PhysicsSystemError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingLayers => write!(f, "no layer interfaces were given"),
+            Self::InvalidMaxBodies(max_bodies) => write!(
+                f,
+                "max_bodies must be between 1 and {}, got {max_bodies}",
+                PhysicsSystemCapacities::MAX_BODIES_LIMIT
+            ),
+            Self::InvalidNumBodyMutexes(num_body_mutexes) => write!(
+                f,
+                "num_body_mutexes must be zero or a power of two, got {num_body_mutexes}"
+            ),
+            Self::ZeroCapacity(name) => write!(f, "{name} must not be zero"),
+            Self::InvalidLayers(err) => write!(f, "invalid layer interfaces: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for PhysicsSystemError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidLayers(err) => Some(err),
+            _ => None,
+        }
+    }
+}
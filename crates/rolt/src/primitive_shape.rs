@@ -0,0 +1,384 @@
+use std::ptr;
+
+use joltc_sys::*;
+
+use crate::shape::create_shape;
+use crate::{IntoJolt, IntoRolt, PhysicsMaterialRef, Shape, ShapeError, Vec3};
+
+/// Settings for a capsule whose two ends have different radii, like a
+/// character's limb.
+///
+/// If both radii are the same, Jolt creates a regular capsule instead.
+///
+/// See also: Jolt's [`TaperedCapsuleShapeSettings`](https://jrouwe.github.io/JoltPhysicsDocs/5.1.0/class_tapered_capsule_shape_settings.html) class.
+#[derive(Debug, Clone)]
+pub struct TaperedCapsuleShapeSettings {
+    pub user_data: u64,
+
+    /// Material of the whole shape, or `None` for Jolt's default material.
+    pub material: Option<PhysicsMaterialRef>,
+    pub density: f32,
+
+    /// Half of the distance between the centers of the top and bottom
+    /// spheres.
+    pub half_height_of_tapered_cylinder: f32,
+    pub top_radius: f32,
+    pub bottom_radius: f32,
+}
+
+impl TaperedCapsuleShapeSettings {
+    pub fn new(half_height_of_tapered_cylinder: f32, top_radius: f32, bottom_radius: f32) -> Self {
+        let defaults = JPC_TaperedCapsuleShapeSettings::default();
+
+        Self {
+            user_data: defaults.UserData,
+            material: None,
+            density: defaults.Density,
+            half_height_of_tapered_cylinder,
+            top_radius,
+            bottom_radius,
+        }
+    }
+
+    /// Creates the shape, or returns [`ShapeError::Invalid`] if a radius or
+    /// the height isn't positive, or if one end's sphere is inside the
+    /// other's.
+    pub fn create(&self) -> Result<Shape, ShapeError> {
+        self.validate()?;
+
+        let settings = JPC_TaperedCapsuleShapeSettings {
+            UserData: self.user_data,
+            Material: self
+                .material
+                .as_ref()
+                .map_or(ptr::null(), PhysicsMaterialRef::raw),
+            Density: self.density,
+            HalfHeightOfTaperedCylinder: self.half_height_of_tapered_cylinder,
+            TopRadius: self.top_radius,
+            BottomRadius: self.bottom_radius,
+            ..Default::default()
+        };
+
+        create_shape(|shape, err| unsafe {
+            JPC_TaperedCapsuleShapeSettings_Create(&settings, shape, err)
+        })
+    }
+
+    /// Checks the settings like [`Self::create`] does, without creating the
+    /// shape.
+    pub fn validate(&self) -> Result<(), ShapeError> {
+        check_density(self.density)?;
+        check(is_positive(self.top_radius), "top radius must be positive")?;
+        check(
+            is_positive(self.bottom_radius),
+            "bottom radius must be positive",
+        )?;
+        check(
+            is_positive(self.half_height_of_tapered_cylinder),
+            "half height must be positive",
+        )?;
+
+        let min_radius = self.top_radius.min(self.bottom_radius);
+        let max_radius = self.top_radius.max(self.bottom_radius);
+        check(
+            2.0 * self.half_height_of_tapered_cylinder + min_radius > max_radius,
+            "one sphere is inside the other, use a sphere shape instead",
+        )
+    }
+}
+
+impl Default for TaperedCapsuleShapeSettings {
+    fn default() -> Self {
+        let defaults = JPC_TaperedCapsuleShapeSettings::default();
+
+        Self {
+            user_data: defaults.UserData,
+            material: None,
+            density: defaults.Density,
+            half_height_of_tapered_cylinder: defaults.HalfHeightOfTaperedCylinder,
+            top_radius: defaults.TopRadius,
+            bottom_radius: defaults.BottomRadius,
+        }
+    }
+}
+
+/// Settings for a cylinder whose top and bottom have different radii. A
+/// radius of zero makes a cone.
+///
+/// If both radii are the same, Jolt creates a regular cylinder instead.
+///
+/// See also: Jolt's [`TaperedCylinderShapeSettings`](https://jrouwe.github.io/JoltPhysicsDocs/5.1.0/class_tapered_cylinder_shape_settings.html) class.
+#[derive(Debug, Clone)]
+pub struct TaperedCylinderShapeSettings {
+    pub user_data: u64,
+
+    /// Material of the whole shape, or `None` for Jolt's default material.
+    pub material: Option<PhysicsMaterialRef>,
+    pub density: f32,
+    pub half_height: f32,
+    pub top_radius: f32,
+    pub bottom_radius: f32,
+
+    /// Radius by which the edges are rounded. It must not be larger than the
+    /// half height or either radius.
+    pub convex_radius: f32,
+}
+
+impl TaperedCylinderShapeSettings {
+    pub fn new(half_height: f32, top_radius: f32, bottom_radius: f32) -> Self {
+        let defaults = JPC_TaperedCylinderShapeSettings::default();
+
+        Self {
+            user_data: defaults.UserData,
+            material: None,
+            density: defaults.Density,
+            half_height,
+            top_radius,
+            bottom_radius,
+            convex_radius: defaults
+                .ConvexRadius
+                .min(half_height)
+                .min(top_radius)
+                .min(bottom_radius)
+                .max(0.0),
+        }
+    }
+
+    /// Creates the shape, or returns [`ShapeError::Invalid`] if a size is
+    /// negative or the convex radius is too large.
+    pub fn create(&self) -> Result<Shape, ShapeError> {
+        self.validate()?;
+
+        let settings = JPC_TaperedCylinderShapeSettings {
+            UserData: self.user_data,
+            Material: self
+                .material
+                .as_ref()
+                .map_or(ptr::null(), PhysicsMaterialRef::raw),
+            Density: self.density,
+            HalfHeight: self.half_height,
+            TopRadius: self.top_radius,
+            BottomRadius: self.bottom_radius,
+            ConvexRadius: self.convex_radius,
+            ..Default::default()
+        };
+
+        create_shape(|shape, err| unsafe {
+            JPC_TaperedCylinderShapeSettings_Create(&settings, shape, err)
+        })
+    }
+
+    /// Checks the settings like [`Self::create`] does, without creating the
+    /// shape.
+    pub fn validate(&self) -> Result<(), ShapeError> {
+        check_density(self.density)?;
+        check(
+            is_positive(self.half_height),
+            "half height must be positive",
+        )?;
+        check(
+            is_non_negative(self.top_radius),
+            "top radius must not be negative",
+        )?;
+        check(
+            is_non_negative(self.bottom_radius),
+            "bottom radius must not be negative",
+        )?;
+        check(
+            is_positive(self.top_radius.max(self.bottom_radius)),
+            "top and bottom radius can't both be zero",
+        )?;
+        check(
+            is_non_negative(self.convex_radius),
+            "convex radius must not be negative",
+        )?;
+        check(
+            self.convex_radius <= self.half_height,
+            "convex radius must not be larger than the half height",
+        )?;
+        check(
+            self.convex_radius <= self.top_radius.min(self.bottom_radius),
+            "convex radius must not be larger than the top or bottom radius",
+        )
+    }
+}
+
+impl Default for TaperedCylinderShapeSettings {
+    fn default() -> Self {
+        let defaults = JPC_TaperedCylinderShapeSettings::default();
+
+        Self {
+            user_data: defaults.UserData,
+            material: None,
+            density: defaults.Density,
+            half_height: defaults.HalfHeight,
+            top_radius: defaults.TopRadius,
+            bottom_radius: defaults.BottomRadius,
+            convex_radius: defaults.ConvexRadius,
+        }
+    }
+}
+
+/// Settings for a plane that collides with everything behind it, like the
+/// floor or walls of a level.
+///
+/// The plane is made of the points `p` where `normal.dot(p) + constant` is
+/// zero. Planes can only be used by static bodies.
+///
+/// See also: Jolt's [`PlaneShapeSettings`](https://jrouwe.github.io/JoltPhysicsDocs/5.1.0/class_plane_shape_settings.html) class.
+#[derive(Debug, Clone)]
+pub struct PlaneShapeSettings {
+    pub user_data: u64,
+
+    /// Material of the whole shape, or `None` for Jolt's default material.
+    pub material: Option<PhysicsMaterialRef>,
+    pub normal: Vec3,
+    pub constant: f32,
+
+    /// Half of the size of the plane along its surface. The plane is not
+    /// really infinite so that its bounds stay usable by the broad phase.
+    pub half_extent: f32,
+}
+
+impl PlaneShapeSettings {
+    pub fn new(normal: Vec3, constant: f32) -> Self {
+        let defaults = JPC_PlaneShapeSettings::default();
+
+        Self {
+            user_data: defaults.UserData,
+            material: None,
+            normal,
+            constant,
+            half_extent: defaults.HalfExtent,
+        }
+    }
+
+    /// Creates a plane with the given normal that goes through `point`.
+    pub fn from_point_and_normal(point: Vec3, normal: Vec3) -> Self {
+        Self::new(normal, -normal.dot(point))
+    }
+
+    /// Creates the shape, or returns [`ShapeError::Invalid`] if the normal
+    /// isn't normalized or the half extent isn't positive.
+    pub fn create(&self) -> Result<Shape, ShapeError> {
+        self.validate()?;
+
+        let settings = JPC_PlaneShapeSettings {
+            UserData: self.user_data,
+            Material: self
+                .material
+                .as_ref()
+                .map_or(ptr::null(), PhysicsMaterialRef::raw),
+            Plane: JPC_Plane {
+                Normal: self.normal.into_jolt(),
+                Constant: self.constant,
+            },
+            HalfExtent: self.half_extent,
+            ..Default::default()
+        };
+
+        create_shape(|shape, err| unsafe { JPC_PlaneShapeSettings_Create(&settings, shape, err) })
+    }
+
+    /// Checks the settings like [`Self::create`] does, without creating the
+    /// shape.
+    pub fn validate(&self) -> Result<(), ShapeError> {
+        check(self.normal.is_normalized(), "normal must be normalized")?;
+        check(self.constant.is_finite(), "constant must be finite")?;
+        check(
+            is_positive(self.half_extent),
+            "half extent must be positive",
+        )
+    }
+}
+
+impl Default for PlaneShapeSettings {
+    fn default() -> Self {
+        let defaults = JPC_PlaneShapeSettings::default();
+
+        Self {
+            user_data: defaults.UserData,
+            material: None,
+            normal: defaults.Plane.Normal.into_rolt(),
+            constant: defaults.Plane.Constant,
+            half_extent: defaults.HalfExtent,
+        }
+    }
+}
+
+/// Settings for a shape that collides with nothing, for bodies that only
+/// need a position, like the anchor of a constraint.
+///
+/// See also: Jolt's [`EmptyShapeSettings`](https://jrouwe.github.io/JoltPhysicsDocs/5.1.0/class_empty_shape_settings.html) class.
+#[derive(Debug, Clone)]
+pub struct EmptyShapeSettings {
+    pub user_data: u64,
+    pub center_of_mass: Vec3,
+}
+
+impl EmptyShapeSettings {
+    pub fn new(center_of_mass: Vec3) -> Self {
+        let defaults = JPC_EmptyShapeSettings::default();
+
+        Self {
+            user_data: defaults.UserData,
+            center_of_mass,
+        }
+    }
+
+    /// Creates the shape, or returns [`ShapeError::Invalid`] if the center of
+    /// mass isn't finite.
+    pub fn create(&self) -> Result<Shape, ShapeError> {
+        self.validate()?;
+
+        let settings = JPC_EmptyShapeSettings {
+            UserData: self.user_data,
+            CenterOfMass: self.center_of_mass.into_jolt(),
+            ..Default::default()
+        };
+
+        create_shape(|shape, err| unsafe { JPC_EmptyShapeSettings_Create(&settings, shape, err) })
+    }
+
+    /// Checks the settings like [`Self::create`] does, without creating the
+    /// shape.
+    pub fn validate(&self) -> Result<(), ShapeError> {
+        check(
+            self.center_of_mass.is_finite(),
+            "center of mass must be finite",
+        )
+    }
+}
+
+impl Default for EmptyShapeSettings {
+    fn default() -> Self {
+        let defaults = JPC_EmptyShapeSettings::default();
+
+        Self {
+            user_data: defaults.UserData,
+            center_of_mass: defaults.CenterOfMass.into_rolt(),
+        }
+    }
+}
+
+fn check(condition: bool, message: &str) -> Result<(), ShapeError> {
+    if condition {
+        Ok(())
+    } else {
+        Err(ShapeError::Invalid(message.to_owned()))
+    }
+}
+
+fn check_density(density: f32) -> Result<(), ShapeError> {
+    check(is_positive(density), "density must be positive")
+}
+
+// These are false for NaN, which Jolt would otherwise accept and then
+// misbehave with.
+fn is_positive(value: f32) -> bool {
+    value.is_finite() && value > 0.0
+}
+
+fn is_non_negative(value: f32) -> bool {
+    value.is_finite() && value >= 0.0
+}
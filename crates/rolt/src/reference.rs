@@ -35,6 +35,16 @@ unsafe impl RefTarget for JPC_MutableCompoundShape {
     }
 }
 
+unsafe impl RefTarget for JPC_PhysicsMaterial {
+    unsafe fn add_ref(value: *const Self) {
+        JPC_PhysicsMaterial_AddRef(value);
+    }
+
+    unsafe fn release(value: *const Self) {
+        JPC_PhysicsMaterial_Release(value);
+    }
+}
+
 /// Rust equivalent to Jolt's [`RefConst`](https://jrouwe.github.io/JoltPhysicsDocs/5.1.0/class_ref_const.html)
 pub struct RefConst<T: RefTarget> {
     ptr: *const T,
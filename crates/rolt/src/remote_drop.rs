@@ -6,7 +6,7 @@ pub(crate) struct RemoteDrop {
 }
 
 impl RemoteDrop {
-    pub unsafe fn new<T: 'static>(this: *mut T) -> Self {
+    pub unsafe fn new<T>(this: *mut T) -> Self {
         Self {
             this: this.cast(),
             drop: drop_adapter::<T>,
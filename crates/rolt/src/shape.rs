@@ -0,0 +1,466 @@
+use std::ffi::CStr;
+use std::fmt;
+use std::ptr;
+
+use joltc_sys::*;
+
+use crate::math::rmat44_rotation_translation;
+use crate::panic_guard::checked_call;
+use crate::{
+    AABox, BodyId, FromJolt, HeightFieldShape, IntoJolt, IntoRolt, Mat4, PhysicsMaterialRef, Quat,
+    RVec3, RefConst, SubShapeId, Vec3,
+};
+
+/// A reference-counted handle to an immutable shape.
+///
+/// See also: Jolt's [`Shape`](https://jrouwe.github.io/JoltPhysicsDocs/5.1.0/class_shape.html) class.
+#[derive(Clone)]
+pub struct Shape {
+    raw: RefConst<JPC_Shape>,
+}
+
+impl Shape {
+    /// Wraps a shape pointer and starts reference counting it.
+    ///
+    /// # Safety
+    ///
+    /// `raw` must point to a live shape.
+    pub unsafe fn from_raw(raw: *const JPC_Shape) -> Self {
+        Self {
+            raw: RefConst::from_active(raw),
+        }
+    }
+
+    pub fn shape_type(&self) -> ShapeType {
+        unsafe { JPC_Shape_GetType(self.raw()).into_rolt() }
+    }
+
+    pub fn sub_type(&self) -> ShapeSubType {
+        unsafe { JPC_Shape_GetSubType(self.raw()).into_rolt() }
+    }
+
+    /// True if this is a convex shape, like a box, sphere or convex hull.
+    pub fn is_convex(&self) -> bool {
+        self.shape_type() == ShapeType::Convex
+    }
+
+    pub fn user_data(&self) -> u64 {
+        unsafe { JPC_Shape_GetUserData(self.raw()) }
+    }
+
+    /// Sets the user data of the shape. Shapes are shared, so this is visible
+    /// from every handle to this shape and every body that uses it.
+    pub fn set_user_data(&self, user_data: u64) {
+        unsafe { JPC_Shape_SetUserData(self.raw().cast_mut(), user_data) }
+    }
+
+    /// Position of the center of mass relative to the shape's origin.
+    pub fn center_of_mass(&self) -> Vec3 {
+        unsafe { JPC_Shape_GetCenterOfMass(self.raw()).into_rolt() }
+    }
+
+    /// Bounding box of the shape, relative to its center of mass.
+    pub fn local_bounds(&self) -> AABox {
+        unsafe { JPC_Shape_GetLocalBounds(self.raw()).into_rolt() }
+    }
+
+    /// Bounding box of the shape when its center of mass is placed at
+    /// `position` with the given rotation and scale.
+    pub fn world_space_bounds(&self, position: RVec3, rotation: Quat, scale: Vec3) -> AABox {
+        let transform = rmat44_rotation_translation(rotation, position);
+
+        unsafe {
+            JPC_Shape_GetWorldSpaceBounds(self.raw(), transform, scale.into_jolt()).into_rolt()
+        }
+    }
+
+    /// Radius of the biggest sphere that fits entirely in the shape.
+    pub fn inner_radius(&self) -> f32 {
+        unsafe { JPC_Shape_GetInnerRadius(self.raw()) }
+    }
+
+    pub fn volume(&self) -> f32 {
+        unsafe { JPC_Shape_GetVolume(self.raw()) }
+    }
+
+    pub fn mass_properties(&self) -> MassProperties {
+        unsafe { JPC_Shape_GetMassProperties(self.raw()).into_rolt() }
+    }
+
+    /// Number of bits needed to encode a [`SubShapeId`] for any leaf of this
+    /// shape.
+    pub fn sub_shape_id_bits_recursive(&self) -> u32 {
+        unsafe { JPC_Shape_GetSubShapeIDBitsRecursive(self.raw()) }
+    }
+
+    /// Memory usage of this shape, not including any child shapes.
+    pub fn stats(&self) -> ShapeStats {
+        unsafe { JPC_Shape_GetStats(self.raw()).into_rolt() }
+    }
+
+    /// Finds the leaf shape identified by `id`, returning it along with the
+    /// part of the ID that the leaf shape uses, like the triangle of a mesh.
+    ///
+    /// Returns `None` if `id` is not valid for this shape.
+    pub fn get_sub_shape(&self, id: SubShapeId) -> Option<(Shape, SubShapeId)> {
+        let mut remainder = SubShapeId::EMPTY.raw();
+
+        unsafe {
+            let leaf = JPC_Shape_GetLeafShape(self.raw(), id.raw(), &mut remainder);
+
+            if leaf.is_null() {
+                None
+            } else {
+                Some((Shape::from_raw(leaf), SubShapeId::new(remainder)))
+            }
+        }
+    }
+
+    /// Returns the user data of the sub-shape identified by `id`.
+    ///
+    /// For compound shapes this is the user data of the child shape, and for
+    /// mesh shapes created with per-triangle user data this is the user data of
+    /// the triangle.
+    pub fn sub_shape_user_data(&self, id: SubShapeId) -> u64 {
+        unsafe { JPC_Shape_GetSubShapeUserData(self.raw(), id.raw()) }
+    }
+
+    /// Returns the material of the sub-shape identified by `id`, or Jolt's
+    /// default material if none was assigned. Returns `None` only if Jolt has
+    /// no default material, which is the case before
+    /// [`register_types`][crate::register_types] is called.
+    ///
+    /// Use [`PhysicsMaterialList::find`][crate::PhysicsMaterialList::find] to
+    /// get the Rust data attached to the material.
+    pub fn material(&self, id: SubShapeId) -> Option<PhysicsMaterialRef> {
+        unsafe { PhysicsMaterialRef::from_raw(JPC_Shape_GetMaterial(self.raw(), id.raw())) }
+    }
+
+    /// True if `scale` can be applied to this shape. Some shapes, like
+    /// spheres and capsules, only support uniform scale, and rotated shapes
+    /// can't be scaled non-uniformly along axes that don't line up with the
+    /// rotation.
+    pub fn is_valid_scale(&self, scale: Vec3) -> bool {
+        unsafe { JPC_Shape_IsValidScale(self.raw(), scale.into_jolt()) }
+    }
+
+    /// Returns the scale closest to `scale` that is valid for this shape.
+    pub fn make_scale_valid(&self, scale: Vec3) -> Vec3 {
+        unsafe { JPC_Shape_MakeScaleValid(self.raw(), scale.into_jolt()).into_rolt() }
+    }
+
+    /// Returns a view of this shape as a compound shape, or `None` if it isn't
+    /// a [`StaticCompoundShape`] or a [`MutableCompoundShape`].
+    ///
+    /// [`StaticCompoundShape`]: https://jrouwe.github.io/JoltPhysicsDocs/5.1.0/class_static_compound_shape.html
+    /// [`MutableCompoundShape`]: https://jrouwe.github.io/JoltPhysicsDocs/5.1.0/class_mutable_compound_shape.html
+    pub fn as_compound(&self) -> Option<CompoundShape> {
+        if self.shape_type() == ShapeType::Compound {
+            Some(CompoundShape {
+                shape: self.clone(),
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Returns a view of this shape as a height field, or `None` if it isn't
+    /// one.
+    pub fn as_height_field(&self) -> Option<HeightFieldShape> {
+        if self.sub_type() == ShapeSubType::HeightField {
+            Some(HeightFieldShape {
+                shape: self.clone(),
+            })
+        } else {
+            None
+        }
+    }
+
+    pub fn raw(&self) -> *const JPC_Shape {
+        self.raw.get()
+    }
+}
+
+/// An error that happened while creating a shape.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ShapeError {
+    /// The shape settings are invalid, as reported by Jolt or by rolt's own
+    /// checks.
+    Invalid(String),
+
+    /// The scale can't be applied to the inner shape.
+    /// [`Shape::make_scale_valid`] can turn it into one that can.
+    UnsupportedScale { scale: Vec3, sub_type: ShapeSubType },
+
+    /// The shape is not of the type that the operation needs.
+    UnexpectedSubType {
+        expected: ShapeSubType,
+        actual: ShapeSubType,
+    },
+}
+
+impl fmt::Display for ShapeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Invalid(message) => write!(f, "invalid shape settings: {message}"),
+            Self::UnsupportedScale { scale, sub_type } => {
+                write!(f, "scale {scale} is not supported by {sub_type:?} shapes")
+            }
+            Self::UnexpectedSubType { expected, actual } => {
+                write!(f, "expected a {expected:?} shape, got a {actual:?} shape")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ShapeError {}
+
+/// Calls one of JoltC's `JPC_*ShapeSettings_Create` functions and turns its
+/// result into a [`Shape`] or a [`ShapeError`].
+pub(crate) fn create_shape(
+    create: impl FnOnce(*mut *mut JPC_Shape, *mut *mut JPC_String) -> bool,
+) -> Result<Shape, ShapeError> {
+    let mut shape: *mut JPC_Shape = ptr::null_mut();
+    let mut err: *mut JPC_String = ptr::null_mut();
+
+    // The shape is owned before any panic is resumed, so that it is released
+    // while unwinding.
+    checked_call(|| unsafe {
+        if create(&mut shape, &mut err) {
+            Ok(Shape::from_raw(shape))
+        } else {
+            let message = CStr::from_ptr(JPC_String_c_str(err))
+                .to_string_lossy()
+                .into_owned();
+            JPC_String_delete(err);
+
+            Err(ShapeError::Invalid(message))
+        }
+    })
+}
+
+/// The broad category of a shape.
+///
+/// See also: Jolt's [`EShapeType`](https://jrouwe.github.io/JoltPhysicsDocs/5.1.0/_shape_8h.html) enum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ShapeType {
+    Convex,
+    Compound,
+    Decorated,
+    Mesh,
+    HeightField,
+    SoftBody,
+    User1,
+    User2,
+    User3,
+    User4,
+
+    /// A type that this version of rolt doesn't know about, holding Jolt's
+    /// raw value.
+    Unknown(JPC_ShapeType),
+}
+
+impl FromJolt for ShapeType {
+    type Jolt = JPC_ShapeType;
+
+    fn from_jolt(value: Self::Jolt) -> Self {
+        match value {
+            JPC_SHAPE_TYPE_CONVEX => Self::Convex,
+            JPC_SHAPE_TYPE_COMPOUND => Self::Compound,
+            JPC_SHAPE_TYPE_DECORATED => Self::Decorated,
+            JPC_SHAPE_TYPE_MESH => Self::Mesh,
+            JPC_SHAPE_TYPE_HEIGHT_FIELD => Self::HeightField,
+            JPC_SHAPE_TYPE_SOFT_BODY => Self::SoftBody,
+            JPC_SHAPE_TYPE_USER1 => Self::User1,
+            JPC_SHAPE_TYPE_USER2 => Self::User2,
+            JPC_SHAPE_TYPE_USER3 => Self::User3,
+            JPC_SHAPE_TYPE_USER4 => Self::User4,
+            _ => Self::Unknown(value),
+        }
+    }
+}
+
+/// The concrete type of a shape.
+///
+/// See also: Jolt's [`EShapeSubType`](https://jrouwe.github.io/JoltPhysicsDocs/5.1.0/_shape_8h.html) enum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ShapeSubType {
+    Sphere,
+    Box,
+    Triangle,
+    Capsule,
+    TaperedCapsule,
+    Cylinder,
+    ConvexHull,
+    StaticCompound,
+    MutableCompound,
+    RotatedTranslated,
+    Scaled,
+    OffsetCenterOfMass,
+    Mesh,
+    HeightField,
+    SoftBody,
+    Plane,
+    TaperedCylinder,
+    Empty,
+
+    /// One of Jolt's `User1` through `User8` sub types, numbered from 1.
+    User(u8),
+
+    /// One of Jolt's `UserConvex1` through `UserConvex8` sub types, numbered
+    /// from 1.
+    UserConvex(u8),
+
+    /// A sub type that this version of rolt doesn't know about, holding
+    /// Jolt's raw value.
+    Unknown(JPC_ShapeSubType),
+}
+
+impl FromJolt for ShapeSubType {
+    type Jolt = JPC_ShapeSubType;
+
+    fn from_jolt(value: Self::Jolt) -> Self {
+        match value {
+            JPC_SHAPE_SUB_TYPE_SPHERE => Self::Sphere,
+            JPC_SHAPE_SUB_TYPE_BOX => Self::Box,
+            JPC_SHAPE_SUB_TYPE_TRIANGLE => Self::Triangle,
+            JPC_SHAPE_SUB_TYPE_CAPSULE => Self::Capsule,
+            JPC_SHAPE_SUB_TYPE_TAPERED_CAPSULE => Self::TaperedCapsule,
+            JPC_SHAPE_SUB_TYPE_CYLINDER => Self::Cylinder,
+            JPC_SHAPE_SUB_TYPE_CONVEX_HULL => Self::ConvexHull,
+            JPC_SHAPE_SUB_TYPE_STATIC_COMPOUND => Self::StaticCompound,
+            JPC_SHAPE_SUB_TYPE_MUTABLE_COMPOUND => Self::MutableCompound,
+            JPC_SHAPE_SUB_TYPE_ROTATED_TRANSLATED => Self::RotatedTranslated,
+            JPC_SHAPE_SUB_TYPE_SCALED => Self::Scaled,
+            JPC_SHAPE_SUB_TYPE_OFFSET_CENTER_OF_MASS => Self::OffsetCenterOfMass,
+            JPC_SHAPE_SUB_TYPE_MESH => Self::Mesh,
+            JPC_SHAPE_SUB_TYPE_HEIGHT_FIELD => Self::HeightField,
+            JPC_SHAPE_SUB_TYPE_SOFT_BODY => Self::SoftBody,
+            JPC_SHAPE_SUB_TYPE_PLANE => Self::Plane,
+            JPC_SHAPE_SUB_TYPE_TAPERED_CYLINDER => Self::TaperedCylinder,
+            JPC_SHAPE_SUB_TYPE_EMPTY => Self::Empty,
+            JPC_SHAPE_SUB_TYPE_USER1..=JPC_SHAPE_SUB_TYPE_USER8 => {
+                Self::User((value - JPC_SHAPE_SUB_TYPE_USER1) as u8 + 1)
+            }
+            JPC_SHAPE_SUB_TYPE_USER_CONVEX1..=JPC_SHAPE_SUB_TYPE_USER_CONVEX8 => {
+                Self::UserConvex((value - JPC_SHAPE_SUB_TYPE_USER_CONVEX1) as u8 + 1)
+            }
+            _ => Self::Unknown(value),
+        }
+    }
+}
+
+/// The mass and inertia tensor of a shape.
+///
+/// See also: Jolt's [`MassProperties`](https://jrouwe.github.io/JoltPhysicsDocs/5.1.0/class_mass_properties.html) class.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MassProperties {
+    pub mass: f32,
+
+    /// Inertia tensor around the center of mass.
+    pub inertia: Mat4,
+}
+
+impl FromJolt for MassProperties {
+    type Jolt = JPC_MassProperties;
+
+    fn from_jolt(value: Self::Jolt) -> Self {
+        Self {
+            mass: value.Mass,
+            inertia: Mat4::from_jolt(value.Inertia),
+        }
+    }
+}
+
+/// Memory usage information about a shape.
+///
+/// See also: Jolt's [`Shape::Stats`](https://jrouwe.github.io/JoltPhysicsDocs/5.1.0/struct_shape_1_1_stats.html) struct.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShapeStats {
+    /// Amount of memory used by this shape, in bytes.
+    pub size_bytes: usize,
+
+    /// Number of triangles in this shape, when applicable.
+    pub num_triangles: u32,
+}
+
+impl FromJolt for ShapeStats {
+    type Jolt = JPC_ShapeStats;
+
+    fn from_jolt(value: Self::Jolt) -> Self {
+        Self {
+            size_bytes: value.SizeBytes,
+            num_triangles: value.NumTriangles,
+        }
+    }
+}
+
+/// A shape made up of other shapes.
+///
+/// See also: Jolt's [`CompoundShape`](https://jrouwe.github.io/JoltPhysicsDocs/5.1.0/class_compound_shape.html) class.
+#[derive(Clone)]
+pub struct CompoundShape {
+    shape: Shape,
+}
+
+impl CompoundShape {
+    pub fn num_sub_shapes(&self) -> u32 {
+        unsafe { JPC_CompoundShape_GetNumSubShapes(self.raw()) }
+    }
+
+    /// Number of bits this shape uses in a [`SubShapeId`] to store the index
+    /// of a child.
+    pub fn sub_shape_id_bits(&self) -> u32 {
+        let max_index = self.num_sub_shapes().saturating_sub(1);
+        u32::BITS - max_index.leading_zeros()
+    }
+
+    /// Returns the index of the child that `id` refers to, along with the
+    /// remainder of the ID that should be passed to that child.
+    ///
+    /// Returns `None` if `id` does not refer to a child of this shape.
+    pub fn child_index_for(&self, id: SubShapeId) -> Option<(u32, SubShapeId)> {
+        // Decoded here rather than by Jolt, which asserts on indices that are
+        // out of range.
+        let (index, remainder) = id.pop_id(self.sub_shape_id_bits());
+
+        (index < self.num_sub_shapes()).then_some((index, remainder))
+    }
+
+    pub fn as_shape(&self) -> &Shape {
+        &self.shape
+    }
+
+    pub fn raw(&self) -> *const JPC_CompoundShape {
+        self.shape.raw().cast::<JPC_CompoundShape>()
+    }
+}
+
+/// A shape placed in the world, along with the body it belongs to.
+///
+/// See also: Jolt's [`TransformedShape`](https://jrouwe.github.io/JoltPhysicsDocs/5.1.0/class_transformed_shape.html) class.
+#[derive(Clone)]
+pub struct TransformedShape {
+    pub body_id: BodyId,
+    pub shape: Shape,
+
+    /// World space position of the center of mass of the shape.
+    pub position: RVec3,
+    pub rotation: Quat,
+    pub scale: Vec3,
+}
+
+impl TransformedShape {
+    /// # Safety
+    ///
+    /// `raw.Shape` must point to a live shape.
+    pub(crate) unsafe fn from_raw(raw: &JPC_TransformedShape) -> Self {
+        Self {
+            body_id: BodyId::new(raw.BodyID),
+            shape: Shape::from_raw(raw.Shape),
+            position: raw.ShapePositionCOM.into_rolt(),
+            rotation: raw.ShapeRotation.into_rolt(),
+            scale: raw.ShapeScale.into_rolt(),
+        }
+    }
+}
@@ -41,3 +41,95 @@ impl BodyId {
         self.0
     }
 }
+
+/// Identifies a sub-shape within a (possibly compound) shape hierarchy.
+///
+/// Each level of the hierarchy stores the index of its child in the lowest
+/// unused bits of the ID. Use [`SubShapeId::pop_id`] to walk back down the
+/// hierarchy, or [`Shape::get_sub_shape`][crate::Shape::get_sub_shape] and
+/// [`CompoundShape::child_index_for`][crate::CompoundShape::child_index_for]
+/// to do it with the number of bits each shape uses.
+///
+/// See also: Jolt's [`SubShapeID`](https://jrouwe.github.io/JoltPhysicsDocs/5.1.0/class_sub_shape_i_d.html) class.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SubShapeId(JPC_SubShapeID);
+
+impl SubShapeId {
+    /// The ID of a shape with no sub-shapes, or of a hierarchy that has been
+    /// completely popped.
+    pub const EMPTY: Self = Self(JPC_SubShapeID::MAX);
+
+    /// The maximum number of bits that can be stored in an ID.
+    pub const MAX_BITS: u32 = JPC_SubShapeID::BITS;
+
+    pub const fn new(value: JPC_SubShapeID) -> Self {
+        Self(value)
+    }
+
+    pub const fn raw(self) -> JPC_SubShapeID {
+        self.0
+    }
+
+    pub const fn is_empty(self) -> bool {
+        self.0 == Self::EMPTY.0
+    }
+
+    /// Removes the lowest `bits` bits from the ID, returning them along with
+    /// the remainder of the ID.
+    pub const fn pop_id(self, bits: u32) -> (u32, SubShapeId) {
+        assert!(bits <= Self::MAX_BITS);
+
+        // Shifting in 64 bits allows popping all 32 bits at once.
+        let mask_bits = ((1u64 << bits) - 1) as JPC_SubShapeID;
+        let fill_bits = ((Self::EMPTY.0 as u64) << (Self::MAX_BITS - bits)) as JPC_SubShapeID;
+        let remainder = ((self.0 as u64 >> bits) as JPC_SubShapeID) | fill_bits;
+
+        (self.0 & mask_bits, Self(remainder))
+    }
+}
+
+impl Default for SubShapeId {
+    fn default() -> Self {
+        Self::EMPTY
+    }
+}
+
+/// Builds a [`SubShapeId`] while descending a shape hierarchy.
+///
+/// See also: Jolt's [`SubShapeIDCreator`](https://jrouwe.github.io/JoltPhysicsDocs/5.1.0/class_sub_shape_i_d_creator.html) class.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SubShapeIdCreator {
+    id: SubShapeId,
+    current_bit: u32,
+}
+
+impl SubShapeIdCreator {
+    pub const fn new() -> Self {
+        Self {
+            id: SubShapeId::EMPTY,
+            current_bit: 0,
+        }
+    }
+
+    /// Returns a new creator with `value` stored in the next `bits` bits.
+    pub const fn push_id(self, value: u32, bits: u32) -> Self {
+        assert!(self.current_bit + bits <= SubShapeId::MAX_BITS);
+        assert!(bits == 32 || value >> bits == 0);
+
+        let mask = ((1u64 << bits) - 1) << self.current_bit;
+        let id = (self.id.0 as u64 & !mask) | ((value as u64) << self.current_bit);
+
+        Self {
+            id: SubShapeId(id as JPC_SubShapeID),
+            current_bit: self.current_bit + bits,
+        }
+    }
+
+    pub const fn id(self) -> SubShapeId {
+        self.id
+    }
+
+    pub const fn num_bits_written(self) -> u32 {
+        self.current_bit
+    }
+}
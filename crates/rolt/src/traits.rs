@@ -6,10 +6,31 @@ use std::marker::PhantomData;
 use joltc_sys::*;
 use paste::paste;
 
+use crate::panic_guard::catch_panic;
 use crate::remote_drop::RemoteDrop;
-use crate::{Body, BodyId, BroadPhaseLayer, IntoJolt, ObjectLayer};
+use crate::{
+    Body, BodyId, BroadPhaseLayer, CollisionGroup, FromJolt, IntoJolt, ObjectLayer,
+    TransformedShape,
+};
 
 macro_rules! define_impl_struct {
+    (
+        $mutability:ident
+        $base_name:ident: Send + Sync {
+            $($method:ident),* $(,)?
+        }
+    ) => {
+        define_impl_struct!($mutability $base_name { $($method),* });
+
+        paste! {
+            // SAFETY: The trait requires `Send` and `Sync`, so the value behind
+            // the bridge is thread-safe. Callers of `from_raw` and
+            // `new_existing` must uphold the same guarantee.
+            unsafe impl Send for [<$base_name Impl>]<'_> {}
+            unsafe impl Sync for [<$base_name Impl>]<'_> {}
+        }
+    };
+
     (
         $mutability:ident
         $base_name:ident {
@@ -26,27 +47,6 @@ macro_rules! define_impl_struct {
             }
 
             impl [<$base_name Impl>]<'static> {
-                pub fn new<T: $base_name + 'static>(value: T) -> Self {
-                    type Bridge<T> = [< $base_name Bridge >]<T>;
-
-                    let fns = [<JPC_ $base_name Fns>] {
-                        $(
-                            $method: Some(Bridge::<T>::$method as _),
-                        )*
-                    };
-
-                    let this = Box::into_raw(Box::new(value));
-
-                    let raw = unsafe { [<JPC_ $base_name _new>](this.cast::<c_void>(), fns) };
-                    let remote_this = unsafe { RemoteDrop::new(this) };
-
-                    Self {
-                        raw,
-                        remote_this: Some(remote_this),
-                        _marker: PhantomData,
-                    }
-                }
-
                 pub unsafe fn from_raw(this: *$mutability c_void, fns: [<JPC_ $base_name Fns>]) -> Self {
                     let raw = unsafe { [<JPC_ $base_name _new>](this, fns) };
 
@@ -67,6 +67,27 @@ macro_rules! define_impl_struct {
             }
 
             impl<'a> [<$base_name Impl>]<'a> {
+                pub fn new<T: $base_name + 'a>(value: T) -> Self {
+                    type Bridge<T> = [< $base_name Bridge >]<T>;
+
+                    let fns = [<JPC_ $base_name Fns>] {
+                        $(
+                            $method: Some(Bridge::<T>::$method as _),
+                        )*
+                    };
+
+                    let this = Box::into_raw(Box::new(value));
+
+                    let raw = unsafe { [<JPC_ $base_name _new>](this.cast::<c_void>(), fns) };
+                    let remote_this = unsafe { RemoteDrop::new(this) };
+
+                    Self {
+                        raw,
+                        remote_this: Some(remote_this),
+                        _marker: PhantomData,
+                    }
+                }
+
                 pub fn new_borrowed<T: $base_name + 'a>(value: &'a mut T) -> Self {
                     type Bridge<T> = [< $base_name Bridge >]<T>;
 
@@ -111,20 +132,24 @@ macro_rules! define_impl_struct {
                 }
             }
 
-            impl<T> From<T> for [<$base_name Impl>]<'static>
+            impl<'a, T> From<T> for [<$base_name Impl>]<'a>
             where
-                T: $base_name + 'static,
+                T: $base_name + 'a,
             {
                 fn from(value: T) -> Self {
-                    Self::new(value)
+                    [<$base_name Impl>]::new(value)
                 }
             }
         }
     };
 }
 
+/// Jolt calls the listener from its worker threads during
+/// [`PhysicsSystem::update`][crate::PhysicsSystem::update], possibly for
+/// several contacts at once, so it must be `Send` and `Sync`.
+///
 /// See also: Jolt's [`ContactListener`](https://jrouwe.github.io/JoltPhysicsDocs/5.1.0/class_contact_listener.html) class.
-pub trait ContactListener {
+pub trait ContactListener: Send + Sync {
     fn on_contact_validate(
         &self,
         body1: &JPC_Body,
@@ -152,7 +177,7 @@ pub trait ContactListener {
     fn on_contact_removed(&self, sub_shape_pair: &JPC_SubShapeIDPair);
 }
 
-define_impl_struct!(mut ContactListener {
+define_impl_struct!(mut ContactListener: Send + Sync {
     OnContactValidate,
     OnContactAdded,
     OnContactPersisted,
@@ -171,8 +196,10 @@ impl<T: ContactListener> ContactListenerBridge<T> {
         base_offset: JPC_RVec3,
         collision_result: *const JPC_CollideShapeResult,
     ) -> JPC_ValidateResult {
-        let this = this.cast::<T>().as_ref().unwrap();
-        this.on_contact_validate(&*body1, &*body2, base_offset, &*collision_result)
+        catch_panic(JPC_VALIDATE_RESULT_ACCEPT_ALL_CONTACTS, || {
+            let this = this.cast::<T>().as_ref().unwrap();
+            this.on_contact_validate(&*body1, &*body2, base_offset, &*collision_result)
+        })
     }
 
     unsafe extern "C" fn OnContactAdded(
@@ -182,8 +209,10 @@ impl<T: ContactListener> ContactListenerBridge<T> {
         manifold: *const JPC_ContactManifold,
         settings: *mut JPC_ContactSettings,
     ) {
-        let this = this.cast::<T>().as_ref().unwrap();
-        this.on_contact_added(&*body1, &*body2, &*manifold, &mut *settings)
+        catch_panic((), || {
+            let this = this.cast::<T>().as_ref().unwrap();
+            this.on_contact_added(&*body1, &*body2, &*manifold, &mut *settings)
+        })
     }
 
     unsafe extern "C" fn OnContactPersisted(
@@ -193,25 +222,33 @@ impl<T: ContactListener> ContactListenerBridge<T> {
         manifold: *const JPC_ContactManifold,
         settings: *mut JPC_ContactSettings,
     ) {
-        let this = this.cast::<T>().as_ref().unwrap();
-        this.on_contact_persisted(&*body1, &*body2, &*manifold, &mut *settings)
+        catch_panic((), || {
+            let this = this.cast::<T>().as_ref().unwrap();
+            this.on_contact_persisted(&*body1, &*body2, &*manifold, &mut *settings)
+        })
     }
 
     unsafe extern "C" fn OnContactRemoved(
         this: *mut c_void,
         sub_shape_pair: *const JPC_SubShapeIDPair,
     ) {
-        let this = this.cast::<T>().as_ref().unwrap();
-        this.on_contact_removed(&*sub_shape_pair);
+        catch_panic((), || {
+            let this = this.cast::<T>().as_ref().unwrap();
+            this.on_contact_removed(&*sub_shape_pair);
+        })
     }
 }
 
+/// Called from Jolt's worker threads during
+/// [`PhysicsSystem::update`][crate::PhysicsSystem::update], so it must be
+/// `Send` and `Sync`.
+///
 /// See also: Jolt's [`GroupFilter`](https://jrouwe.github.io/JoltPhysicsDocs/5.1.0/class_group_filter.html) class.
-pub trait GroupFilter {
-    fn can_collide(&self, group_1: &JPC_CollisionGroup, group_2: &JPC_CollisionGroup) -> bool;
+pub trait GroupFilter: Send + Sync {
+    fn can_collide(&self, group_1: &CollisionGroup, group_2: &CollisionGroup) -> bool;
 }
 
-define_impl_struct!(const GroupFilter {
+define_impl_struct!(const GroupFilter: Send + Sync {
     CanCollide,
 });
 
@@ -225,21 +262,26 @@ impl<T: GroupFilter> GroupFilterBridge<T> {
         group_1: *const JPC_CollisionGroup,
         group_2: *const JPC_CollisionGroup,
     ) -> bool {
-        let this = this.cast::<T>().as_ref().unwrap();
-        let group_1 = &*group_1;
-        let group_2 = &*group_2;
+        catch_panic(false, || {
+            let this = this.cast::<T>().as_ref().unwrap();
+            let group_1 = CollisionGroup::from_jolt(*group_1);
+            let group_2 = CollisionGroup::from_jolt(*group_2);
 
-        this.can_collide(group_1, group_2)
+            this.can_collide(&group_1, &group_2)
+        })
     }
 }
 
+/// Shared by the whole [`PhysicsSystem`][crate::PhysicsSystem] and called
+/// from any thread that uses it, so it must be `Send` and `Sync`.
+///
 /// See also: Jolt's [`BroadPhaseLayerInterface`](https://jrouwe.github.io/JoltPhysicsDocs/5.1.0/class_broad_phase_layer_interface.html) class.
-pub trait BroadPhaseLayerInterface {
+pub trait BroadPhaseLayerInterface: Send + Sync {
     fn get_num_broad_phase_layers(&self) -> u32;
     fn get_broad_phase_layer(&self, layer: ObjectLayer) -> BroadPhaseLayer;
 }
 
-define_impl_struct!(const BroadPhaseLayerInterface {
+define_impl_struct!(const BroadPhaseLayerInterface: Send + Sync {
     GetNumBroadPhaseLayers,
     GetBroadPhaseLayer,
 });
@@ -250,28 +292,35 @@ struct BroadPhaseLayerInterfaceBridge<T> {
 
 impl<T: BroadPhaseLayerInterface> BroadPhaseLayerInterfaceBridge<T> {
     unsafe extern "C" fn GetNumBroadPhaseLayers(this: *const c_void) -> c_uint {
-        let this = this.cast::<T>().as_ref().unwrap();
+        catch_panic(1, || {
+            let this = this.cast::<T>().as_ref().unwrap();
 
-        this.get_num_broad_phase_layers()
+            this.get_num_broad_phase_layers()
+        })
     }
 
     unsafe extern "C" fn GetBroadPhaseLayer(
         this: *const c_void,
         layer: JPC_ObjectLayer,
     ) -> JPC_BroadPhaseLayer {
-        let this = this.cast::<T>().as_ref().unwrap();
-        let layer = ObjectLayer::new(layer);
+        catch_panic(0, || {
+            let this = this.cast::<T>().as_ref().unwrap();
+            let layer = ObjectLayer::new(layer);
 
-        this.get_broad_phase_layer(layer).raw()
+            this.get_broad_phase_layer(layer).raw()
+        })
     }
 }
 
+/// Shared by the whole [`PhysicsSystem`][crate::PhysicsSystem] and called
+/// from any thread that uses it, so it must be `Send` and `Sync`.
+///
 /// See also: Jolt's [`ObjectVsBroadPhaseLayerFilter`](https://jrouwe.github.io/JoltPhysicsDocs/5.1.0/class_object_vs_broad_phase_layer_filter.html) class.
-pub trait ObjectVsBroadPhaseLayerFilter {
+pub trait ObjectVsBroadPhaseLayerFilter: Send + Sync {
     fn should_collide(&self, layer1: ObjectLayer, layer2: BroadPhaseLayer) -> bool;
 }
 
-define_impl_struct!(const ObjectVsBroadPhaseLayerFilter { ShouldCollide });
+define_impl_struct!(const ObjectVsBroadPhaseLayerFilter: Send + Sync { ShouldCollide });
 
 struct ObjectVsBroadPhaseLayerFilterBridge<T> {
     _phantom: PhantomData<T>,
@@ -283,20 +332,25 @@ impl<T: ObjectVsBroadPhaseLayerFilter> ObjectVsBroadPhaseLayerFilterBridge<T> {
         layer1: JPC_ObjectLayer,
         layer2: JPC_BroadPhaseLayer,
     ) -> bool {
-        let this = this.cast::<T>().as_ref().unwrap();
-        let layer1 = ObjectLayer::new(layer1);
-        let layer2 = BroadPhaseLayer::new(layer2);
+        catch_panic(false, || {
+            let this = this.cast::<T>().as_ref().unwrap();
+            let layer1 = ObjectLayer::new(layer1);
+            let layer2 = BroadPhaseLayer::new(layer2);
 
-        this.should_collide(layer1, layer2)
+            this.should_collide(layer1, layer2)
+        })
     }
 }
 
+/// Shared by the whole [`PhysicsSystem`][crate::PhysicsSystem] and called
+/// from any thread that uses it, so it must be `Send` and `Sync`.
+///
 /// See also: Jolt's [`ObjectLayerPairFilter`](https://jrouwe.github.io/JoltPhysicsDocs/5.1.0/class_object_layer_pair_filter.html) class.
-pub trait ObjectLayerPairFilter {
+pub trait ObjectLayerPairFilter: Send + Sync {
     fn should_collide(&self, layer1: ObjectLayer, layer2: ObjectLayer) -> bool;
 }
 
-define_impl_struct!(const ObjectLayerPairFilter { ShouldCollide });
+define_impl_struct!(const ObjectLayerPairFilter: Send + Sync { ShouldCollide });
 
 struct ObjectLayerPairFilterBridge<T> {
     _phantom: PhantomData<T>,
@@ -308,11 +362,13 @@ impl<T: ObjectLayerPairFilter> ObjectLayerPairFilterBridge<T> {
         layer1: JPC_ObjectLayer,
         layer2: JPC_ObjectLayer,
     ) -> bool {
-        let this = this.cast::<T>().as_ref().unwrap();
-        let layer1 = ObjectLayer::new(layer1);
-        let layer2 = ObjectLayer::new(layer2);
+        catch_panic(false, || {
+            let this = this.cast::<T>().as_ref().unwrap();
+            let layer1 = ObjectLayer::new(layer1);
+            let layer2 = ObjectLayer::new(layer2);
 
-        this.should_collide(layer1, layer2)
+            this.should_collide(layer1, layer2)
+        })
     }
 }
 
@@ -329,10 +385,12 @@ struct BroadPhaseLayerFilterBridge<T> {
 
 impl<T: BroadPhaseLayerFilter> BroadPhaseLayerFilterBridge<T> {
     unsafe extern "C" fn ShouldCollide(this: *const c_void, layer: JPC_BroadPhaseLayer) -> bool {
-        let this = this.cast::<T>().as_ref().unwrap();
-        let layer = BroadPhaseLayer::new(layer);
+        catch_panic(false, || {
+            let this = this.cast::<T>().as_ref().unwrap();
+            let layer = BroadPhaseLayer::new(layer);
 
-        this.should_collide(layer)
+            this.should_collide(layer)
+        })
     }
 }
 
@@ -349,10 +407,12 @@ struct ObjectLayerFilterBridge<T> {
 
 impl<T: ObjectLayerFilter> ObjectLayerFilterBridge<T> {
     unsafe extern "C" fn ShouldCollide(this: *const c_void, layer: JPC_ObjectLayer) -> bool {
-        let this = this.cast::<T>().as_ref().unwrap();
-        let layer = ObjectLayer::new(layer);
+        catch_panic(false, || {
+            let this = this.cast::<T>().as_ref().unwrap();
+            let layer = ObjectLayer::new(layer);
 
-        this.should_collide(layer)
+            this.should_collide(layer)
+        })
     }
 }
 
@@ -373,19 +433,23 @@ struct BodyFilterBridge<T> {
 
 impl<T: BodyFilter> BodyFilterBridge<T> {
     unsafe extern "C" fn ShouldCollide(this: *const c_void, body_id: JPC_BodyID) -> bool {
-        let this = this.cast::<T>().as_ref().unwrap();
-        let body_id = BodyId::new(body_id);
+        catch_panic(false, || {
+            let this = this.cast::<T>().as_ref().unwrap();
+            let body_id = BodyId::new(body_id);
 
-        this.should_collide(body_id)
+            this.should_collide(body_id)
+        })
     }
 
     unsafe extern "C" fn ShouldCollideLocked(this: *const c_void, body: *const JPC_Body) -> bool {
-        let this = this.cast::<T>().as_ref().unwrap();
+        catch_panic(false, || {
+            let this = this.cast::<T>().as_ref().unwrap();
 
-        // FIXME: cast_mut should not be required here
-        let mut body = Body::new(body.cast_mut());
+            // FIXME: cast_mut should not be required here
+            let mut body = Body::new(body.cast_mut());
 
-        this.should_collide_locked(&mut body)
+            this.should_collide_locked(&mut body)
+        })
     }
 }
 
@@ -422,9 +486,11 @@ impl<T: ShapeFilter> ShapeFilterBridge<T> {
         shape2: *const JPC_Shape,
         subshape_id2: JPC_SubShapeID,
     ) -> bool {
-        let this = this.cast::<T>().as_ref().unwrap();
+        catch_panic(false, || {
+            let this = this.cast::<T>().as_ref().unwrap();
 
-        this.should_collide(shape2, subshape_id2)
+            this.should_collide(shape2, subshape_id2)
+        })
     }
 
     unsafe extern "C" fn ShouldCollideTwoShapes(
@@ -434,14 +500,20 @@ impl<T: ShapeFilter> ShapeFilterBridge<T> {
         shape2: *const JPC_Shape,
         subshape_id2: JPC_SubShapeID,
     ) -> bool {
-        let this = this.cast::<T>().as_ref().unwrap();
+        catch_panic(false, || {
+            let this = this.cast::<T>().as_ref().unwrap();
 
-        this.should_collide_two_shapes(shape1, subshape_id1, shape2, subshape_id2)
+            this.should_collide_two_shapes(shape1, subshape_id1, shape2, subshape_id2)
+        })
     }
 }
 
+/// Called from Jolt's worker threads during
+/// [`PhysicsSystem::update`][crate::PhysicsSystem::update], so it must be
+/// `Send` and `Sync`.
+///
 /// See also: Jolt's [`SimShapeFilter`](https://jrouwe.github.io/JoltPhysicsDocs/5.1.0/class_sim_shape_filter.html) class.
-pub trait SimShapeFilter {
+pub trait SimShapeFilter: Send + Sync {
     fn should_collide(
         &self,
         body1: Body<'_>,
@@ -453,7 +525,7 @@ pub trait SimShapeFilter {
     ) -> bool;
 }
 
-define_impl_struct!(const SimShapeFilter {
+define_impl_struct!(const SimShapeFilter: Send + Sync {
     ShouldCollide,
 });
 
@@ -471,13 +543,15 @@ impl<T: SimShapeFilter> SimShapeFilterBridge<T> {
         shape2: *const JPC_Shape,
         subshape2: JPC_SubShapeID,
     ) -> bool {
-        let this = this.cast::<T>().as_ref().unwrap();
+        catch_panic(false, || {
+            let this = this.cast::<T>().as_ref().unwrap();
 
-        // FIXME: `Body` should support a `const` version!
-        let body1 = Body::new(body1.cast_mut());
-        let body2 = Body::new(body2.cast_mut());
+            // FIXME: `Body` should support a `const` version!
+            let body1 = Body::new(body1.cast_mut());
+            let body2 = Body::new(body2.cast_mut());
 
-        this.should_collide(body1, shape1, subshape1, body2, shape2, subshape2)
+            this.should_collide(body1, shape1, subshape1, body2, shape2, subshape2)
+        })
     }
 }
 
@@ -510,17 +584,21 @@ impl<T: CastShapeCollector> CastShapeCollectorBridge<T> {
         base: *mut JPC_CastShapeCollector,
         result: *const JPC_ShapeCastResult,
     ) {
-        let this = this.cast::<T>().as_mut().unwrap();
-        let mut base = CastShapeBase { base };
-        let result = &*result;
+        catch_panic((), || {
+            let this = this.cast::<T>().as_mut().unwrap();
+            let mut base = CastShapeBase { base };
+            let result = &*result;
 
-        this.add_hit(&mut base, result);
+            this.add_hit(&mut base, result);
+        })
     }
 
     unsafe extern "C" fn Reset(this: *mut c_void) {
-        let this = this.cast::<T>().as_mut().unwrap();
+        catch_panic((), || {
+            let this = this.cast::<T>().as_mut().unwrap();
 
-        this.reset();
+            this.reset();
+        })
     }
 }
 
@@ -553,16 +631,114 @@ impl<T: CollideShapeCollector> CollideShapeCollectorBridge<T> {
         base: *mut JPC_CollideShapeCollector,
         result: *const JPC_CollideShapeResult,
     ) {
-        let this = this.cast::<T>().as_mut().unwrap();
-        let mut base = CollideShapeBase { base };
-        let result = &*result;
+        catch_panic((), || {
+            let this = this.cast::<T>().as_mut().unwrap();
+            let mut base = CollideShapeBase { base };
+            let result = &*result;
+
+            this.add_hit(&mut base, result);
+        })
+    }
+
+    unsafe extern "C" fn Reset(this: *mut c_void) {
+        catch_panic((), || {
+            let this = this.cast::<T>().as_mut().unwrap();
+
+            this.reset();
+        })
+    }
+}
+
+pub trait CollidePointCollector {
+    fn reset(&mut self);
+    fn add_hit(&mut self, base: &mut CollidePointBase, result: &JPC_CollidePointResult);
+}
+
+pub struct CollidePointBase {
+    base: *mut JPC_CollidePointCollector,
+}
+
+impl CollidePointBase {
+    pub fn update_early_out_fraction(&mut self, fraction: f32) {
+        unsafe {
+            JPC_CollidePointCollector_UpdateEarlyOutFraction(self.base, fraction);
+        }
+    }
+}
+
+define_impl_struct!(mut CollidePointCollector { Reset, AddHit });
+
+struct CollidePointCollectorBridge<T> {
+    _phantom: PhantomData<T>,
+}
+
+impl<T: CollidePointCollector> CollidePointCollectorBridge<T> {
+    unsafe extern "C" fn AddHit(
+        this: *mut c_void,
+        base: *mut JPC_CollidePointCollector,
+        result: *const JPC_CollidePointResult,
+    ) {
+        catch_panic((), || {
+            let this = this.cast::<T>().as_mut().unwrap();
+            let mut base = CollidePointBase { base };
+            let result = &*result;
+
+            this.add_hit(&mut base, result);
+        })
+    }
+
+    unsafe extern "C" fn Reset(this: *mut c_void) {
+        catch_panic((), || {
+            let this = this.cast::<T>().as_mut().unwrap();
+
+            this.reset();
+        })
+    }
+}
+
+pub trait TransformedShapeCollector {
+    fn reset(&mut self);
+    fn add_hit(&mut self, base: &mut TransformedShapeBase, result: TransformedShape);
+}
+
+pub struct TransformedShapeBase {
+    base: *mut JPC_TransformedShapeCollector,
+}
+
+impl TransformedShapeBase {
+    pub fn update_early_out_fraction(&mut self, fraction: f32) {
+        unsafe {
+            JPC_TransformedShapeCollector_UpdateEarlyOutFraction(self.base, fraction);
+        }
+    }
+}
+
+define_impl_struct!(mut TransformedShapeCollector { Reset, AddHit });
+
+struct TransformedShapeCollectorBridge<T> {
+    _phantom: PhantomData<T>,
+}
+
+impl<T: TransformedShapeCollector> TransformedShapeCollectorBridge<T> {
+    unsafe extern "C" fn AddHit(
+        this: *mut c_void,
+        base: *mut JPC_TransformedShapeCollector,
+        result: *const JPC_TransformedShape,
+    ) {
+        catch_panic((), || {
+            let this = this.cast::<T>().as_mut().unwrap();
+            let mut base = TransformedShapeBase { base };
+            let result = TransformedShape::from_raw(&*result);
 
-        this.add_hit(&mut base, result);
+            this.add_hit(&mut base, result);
+        })
     }
 
     unsafe extern "C" fn Reset(this: *mut c_void) {
-        let this = this.cast::<T>().as_mut().unwrap();
+        catch_panic((), || {
+            let this = this.cast::<T>().as_mut().unwrap();
 
-        this.reset();
+            this.reset();
+        })
     }
 }
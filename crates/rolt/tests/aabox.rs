@@ -0,0 +1,24 @@
+use std::f32::consts::FRAC_PI_2;
+
+use rolt::{AABox, Quat, Vec3};
+
+#[test]
+fn transformed_rotates_extent() {
+    let bounds = AABox::new(Vec3::new(-1.0, 0.0, -2.0), Vec3::new(1.0, 1.0, 2.0));
+    let transformed =
+        bounds.transformed(Quat::from_rotation_y(FRAC_PI_2), Vec3::new(10.0, 0.0, 0.0));
+
+    assert!(transformed.min.abs_diff_eq(Vec3::new(8.0, 0.0, -1.0), 1e-5));
+    assert!(transformed.max.abs_diff_eq(Vec3::new(12.0, 1.0, 1.0), 1e-5));
+}
+
+#[test]
+fn encapsulate_grows_to_fit() {
+    let mut bounds = AABox::new(Vec3::ZERO, Vec3::ONE);
+    bounds.encapsulate(&AABox::new(Vec3::splat(-1.0), Vec3::new(0.5, 2.0, 0.5)));
+
+    assert_eq!(
+        bounds,
+        AABox::new(Vec3::splat(-1.0), Vec3::new(1.0, 2.0, 1.0))
+    );
+}
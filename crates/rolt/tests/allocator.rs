@@ -0,0 +1,28 @@
+use rolt::TaperedCapsuleShapeSettings;
+
+#[test]
+fn counts_jolt_allocations() {
+    assert_eq!(rolt::memory_stats(), None);
+
+    // SAFETY: Nothing else in this test binary uses Jolt.
+    unsafe { rolt::register_rust_allocator() };
+    rolt::factory_init();
+    rolt::register_types();
+
+    let before = rolt::memory_stats().unwrap();
+
+    let shape = TaperedCapsuleShapeSettings::new(1.0, 0.5, 0.25)
+        .create()
+        .unwrap();
+    let with_shape = rolt::memory_stats().unwrap();
+    assert!(with_shape.live_bytes > before.live_bytes);
+    assert!(with_shape.total_allocations > before.total_allocations);
+    assert!(with_shape.peak_bytes >= with_shape.live_bytes);
+
+    drop(shape);
+    let after = rolt::memory_stats().unwrap();
+    assert!(after.live_bytes < with_shape.live_bytes);
+
+    rolt::unregister_types();
+    rolt::factory_delete();
+}
@@ -0,0 +1,55 @@
+use joltc_sys::JPC_GroupFilter;
+use rolt::{CollisionGroup, GroupFilter, GroupFilterTable};
+
+fn group(group_id: u32, sub_group_id: u32) -> CollisionGroup {
+    CollisionGroup {
+        group_id,
+        sub_group_id,
+        ..CollisionGroup::NONE
+    }
+}
+
+#[test]
+fn table_disables_sub_group_pairs() {
+    let mut table = GroupFilterTable::new(4);
+    table.disable_collision(0, 1);
+    table.disable_collision(3, 2);
+
+    assert!(!table.is_collision_enabled(1, 0));
+    assert!(!table.is_collision_enabled(2, 3));
+    assert!(table.is_collision_enabled(0, 2));
+    assert!(!table.is_collision_enabled(1, 1));
+    assert!(table.is_collision_enabled(1, 100));
+
+    table.enable_collision(1, 0);
+    assert!(table.is_collision_enabled(0, 1));
+}
+
+#[test]
+fn table_only_filters_within_a_group() {
+    let mut table = GroupFilterTable::new(100);
+    table.disable_collision(98, 99);
+
+    assert!(!table.can_collide(&group(7, 98), &group(7, 99)));
+    assert!(table.can_collide(&group(7, 98), &group(8, 99)));
+    assert!(table.can_collide(&group(7, 98), &group(7, CollisionGroup::INVALID_SUB_GROUP)));
+}
+
+#[test]
+fn table_checks_groups_in_jolt_order() {
+    let table = GroupFilterTable::new(4);
+
+    // A body without a group collides with everything.
+    let invalid = group(CollisionGroup::INVALID_GROUP, 1);
+    assert!(table.can_collide(&invalid, &invalid));
+
+    // The same sub-group of one group never collides with itself.
+    assert!(!table.can_collide(&group(7, 1), &group(7, 1)));
+
+    // Groups that belong to different filters always collide.
+    let other_filter = CollisionGroup {
+        filter: std::ptr::NonNull::<JPC_GroupFilter>::dangling().as_ptr(),
+        ..group(7, 1)
+    };
+    assert!(table.can_collide(&group(7, 1), &other_filter));
+}
@@ -0,0 +1,124 @@
+use rolt::{
+    BroadPhaseLayerInterface, CollisionLayers, CollisionLayersError, ObjectLayer,
+    ObjectLayerPairFilter, ObjectVsBroadPhaseLayerFilter,
+};
+
+fn layers() -> CollisionLayers {
+    CollisionLayers::builder()
+        .broad_phase_layer("non_moving")
+        .broad_phase_layer("moving")
+        .object_layer("static", "non_moving")
+        .object_layer("moving", "moving")
+        .object_layer("debris", "moving")
+        .collide("static", "moving")
+        .collide("moving", "moving")
+        .collide("debris", "static")
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn layers_are_numbered_in_declaration_order() {
+    let layers = layers();
+
+    assert_eq!(layers.object_layer("debris"), Some(ObjectLayer::new(2)));
+    assert_eq!(
+        layers.object_layer_name(ObjectLayer::new(1)),
+        Some("moving")
+    );
+    assert_eq!(layers.get_num_broad_phase_layers(), 2);
+    assert_eq!(
+        layers.get_broad_phase_layer(ObjectLayer::new(2)),
+        layers.broad_phase_layer("moving").unwrap()
+    );
+}
+
+#[test]
+fn pairs_are_symmetric_and_fill_broad_phase_table() {
+    let layers = layers();
+    let layer = |name| layers.object_layer(name).unwrap();
+    let non_moving = layers.broad_phase_layer("non_moving").unwrap();
+    let moving = layers.broad_phase_layer("moving").unwrap();
+
+    assert!(ObjectLayerPairFilter::should_collide(
+        &layers,
+        layer("moving"),
+        layer("static")
+    ));
+    assert!(ObjectLayerPairFilter::should_collide(
+        &layers,
+        layer("static"),
+        layer("debris")
+    ));
+    assert!(!ObjectLayerPairFilter::should_collide(
+        &layers,
+        layer("debris"),
+        layer("moving")
+    ));
+    assert!(!ObjectLayerPairFilter::should_collide(
+        &layers,
+        layer("static"),
+        layer("static")
+    ));
+
+    assert!(!ObjectVsBroadPhaseLayerFilter::should_collide(
+        &layers,
+        layer("static"),
+        non_moving
+    ));
+    assert!(ObjectVsBroadPhaseLayerFilter::should_collide(
+        &layers,
+        layer("static"),
+        moving
+    ));
+    assert!(ObjectVsBroadPhaseLayerFilter::should_collide(
+        &layers,
+        layer("debris"),
+        non_moving
+    ));
+    assert!(!ObjectVsBroadPhaseLayerFilter::should_collide(
+        &layers,
+        layer("debris"),
+        moving
+    ));
+
+    assert!(!layers.object_layers_collide(ObjectLayer::new(3), layer("static")));
+}
+
+#[test]
+fn build_rejects_inconsistent_layers() {
+    let result = CollisionLayers::builder()
+        .broad_phase_layer("bp")
+        .object_layer("a", "missing")
+        .build();
+    assert_eq!(
+        result.unwrap_err(),
+        CollisionLayersError::UnknownBroadPhaseLayer {
+            object_layer: "a".to_owned(),
+            broad_phase_layer: "missing".to_owned(),
+        }
+    );
+
+    let result = CollisionLayers::builder()
+        .broad_phase_layer("bp")
+        .object_layer("a", "bp")
+        .object_layer("a", "bp")
+        .build();
+    assert_eq!(
+        result.unwrap_err(),
+        CollisionLayersError::DuplicateObjectLayer("a".to_owned())
+    );
+
+    let result = CollisionLayers::builder()
+        .broad_phase_layer("bp")
+        .object_layer("a", "bp")
+        .collide("a", "b")
+        .build();
+    assert_eq!(
+        result.unwrap_err(),
+        CollisionLayersError::UnknownObjectLayer("b".to_owned())
+    );
+
+    let result = CollisionLayers::builder().build();
+    assert_eq!(result.unwrap_err(), CollisionLayersError::Empty);
+}
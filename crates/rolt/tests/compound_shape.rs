@@ -0,0 +1,148 @@
+mod framework;
+
+use joltc_sys::JPC_ACTIVATION_DONT_ACTIVATE;
+use rolt::{
+    MutableCompoundShape, MutableCompoundShapeSettings, Quat, RVec3, Shape,
+    StaticCompoundShapeSettings, SubShapeId, SubShapeIdCreator, SubShapeSettings, Vec3,
+};
+
+use crate::framework::*;
+
+fn unit_box() -> Shape {
+    box_shape(Vec3::splat(0.5))
+}
+
+fn mutable_compound() -> MutableCompoundShape {
+    MutableCompoundShapeSettings::new(vec![SubShapeSettings::new(
+        unit_box(),
+        Vec3::ZERO,
+        Quat::IDENTITY,
+    )])
+    .create()
+    .unwrap()
+}
+
+#[test]
+fn add_remove_and_modify_children() {
+    global_init();
+
+    let mut compound = mutable_compound();
+    assert_eq!(compound.num_sub_shapes(), 1);
+
+    // SAFETY: The shape isn't used by any physics system.
+    let index =
+        unsafe { compound.add_shape(Vec3::new(2.0, 0.0, 0.0), Quat::IDENTITY, &unit_box(), 7) };
+    assert_eq!(index, 1);
+    assert_eq!(compound.num_sub_shapes(), 2);
+
+    let bounds = compound.as_shape().local_bounds();
+    assert!(bounds.max.abs_diff_eq(Vec3::new(2.5, 0.5, 0.5), 1.0e-4));
+
+    unsafe { compound.modify_shape(1, Vec3::new(0.0, 3.0, 0.0), Quat::IDENTITY) };
+    let bounds = compound.as_shape().local_bounds();
+    assert!(bounds.max.abs_diff_eq(Vec3::new(0.5, 3.5, 0.5), 1.0e-4));
+
+    unsafe {
+        compound.modify_shapes(
+            0,
+            &[Vec3::new(-1.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0)],
+            &[Quat::IDENTITY, Quat::IDENTITY],
+        )
+    };
+    let bounds = compound.as_shape().local_bounds();
+    assert!(bounds.min.abs_diff_eq(Vec3::new(-1.5, -0.5, -0.5), 1.0e-4));
+    assert!(bounds.max.abs_diff_eq(Vec3::new(1.5, 0.5, 0.5), 1.0e-4));
+
+    unsafe { compound.remove_shape(0) };
+    assert_eq!(compound.num_sub_shapes(), 1);
+    let bounds = compound.as_shape().local_bounds();
+    assert!(bounds.min.abs_diff_eq(Vec3::new(0.5, -0.5, -0.5), 1.0e-4));
+}
+
+#[test]
+#[should_panic(expected = "out of bounds")]
+fn remove_checks_index() {
+    global_init();
+
+    // SAFETY: The shape isn't used by any physics system.
+    unsafe { mutable_compound().remove_shape(1) };
+}
+
+#[test]
+fn from_shape_checks_sub_type() {
+    global_init();
+
+    let static_compound = StaticCompoundShapeSettings::new(vec![SubShapeSettings::new(
+        unit_box(),
+        Vec3::ZERO,
+        Quat::IDENTITY,
+    )])
+    .create()
+    .unwrap();
+
+    let shape = mutable_compound().as_shape();
+
+    // SAFETY: The handle from `create` is gone and the shapes aren't used
+    // anywhere else.
+    unsafe {
+        assert!(MutableCompoundShape::from_shape(static_compound).is_none());
+        assert!(MutableCompoundShape::from_shape(shape).is_some());
+    }
+}
+
+#[test]
+fn modify_and_notify_keeps_body_in_place() {
+    let world = World::new();
+    let mut compound = mutable_compound();
+    let body_id = world.add_static(&compound.as_shape(), RVec3::new(0.0, 5.0, 0.0));
+    let body_interface = world.system.body_interface();
+
+    assert!(compound
+        .as_shape()
+        .center_of_mass()
+        .abs_diff_eq(Vec3::ZERO, 1.0e-5));
+
+    compound.modify_and_notify(
+        &body_interface,
+        body_id,
+        false,
+        JPC_ACTIVATION_DONT_ACTIVATE,
+        // SAFETY: The world isn't stepping or running queries.
+        |compound| unsafe {
+            compound.add_shape(Vec3::new(2.0, 0.0, 0.0), Quat::IDENTITY, &unit_box(), 0);
+            compound.adjust_center_of_mass();
+        },
+    );
+
+    // The center of mass moved halfway to the new child, but the body's
+    // origin didn't.
+    let center_of_mass = compound.as_shape().center_of_mass();
+    assert!(center_of_mass.abs_diff_eq(Vec3::new(1.0, 0.0, 0.0), 1.0e-4));
+
+    let position = body_interface.position(body_id);
+    assert!(position.abs_diff_eq(RVec3::new(0.0, 5.0, 0.0), 1.0e-4));
+
+    let com_position = body_interface.center_of_mass_position(body_id);
+    assert!(com_position.abs_diff_eq(RVec3::new(1.0, 5.0, 0.0), 1.0e-4));
+}
+
+#[test]
+fn child_index_for_checks_range() {
+    global_init();
+
+    let children = (0..3)
+        .map(|i| SubShapeSettings::new(unit_box(), Vec3::new(i as f32, 0.0, 0.0), Quat::IDENTITY))
+        .collect();
+    let shape = StaticCompoundShapeSettings::new(children).create().unwrap();
+    let compound = shape.as_compound().unwrap();
+    assert_eq!(compound.sub_shape_id_bits(), 2);
+
+    let id = SubShapeIdCreator::new().push_id(2, 2).push_id(5, 3).id();
+    let (index, remainder) = compound.child_index_for(id).unwrap();
+    assert_eq!(index, 2);
+    assert_eq!(remainder.pop_id(3), (5, SubShapeId::EMPTY));
+
+    // Index 3 fits in two bits, but there is no fourth child.
+    let id = SubShapeIdCreator::new().push_id(3, 2).id();
+    assert!(compound.child_index_for(id).is_none());
+}
@@ -0,0 +1,177 @@
+mod framework;
+
+use rolt::{ConvexDecomposition, ConvexDecompositionSettings, ShapeSubType, Vec3};
+
+use crate::framework::*;
+
+/// Appends a closed axis-aligned box to a triangle mesh.
+fn push_box(vertices: &mut Vec<Vec3>, triangles: &mut Vec<[u32; 3]>, min: Vec3, max: Vec3) {
+    let base = vertices.len() as u32;
+
+    for i in 0..8 {
+        vertices.push(Vec3::new(
+            if i & 1 == 0 { min.x } else { max.x },
+            if i & 2 == 0 { min.y } else { max.y },
+            if i & 4 == 0 { min.z } else { max.z },
+        ));
+    }
+
+    for [a, b, c, d] in [
+        [0, 2, 3, 1],
+        [4, 5, 7, 6],
+        [0, 1, 5, 4],
+        [2, 6, 7, 3],
+        [0, 4, 6, 2],
+        [1, 3, 7, 5],
+    ] {
+        triangles.push([base + a, base + b, base + c]);
+        triangles.push([base + a, base + c, base + d]);
+    }
+}
+
+fn bounds(points: &[Vec3]) -> (Vec3, Vec3) {
+    points.iter().fold(
+        (Vec3::splat(f32::MAX), Vec3::splat(f32::MIN)),
+        |(min, max), point| (min.min(*point), max.max(*point)),
+    )
+}
+
+fn assert_near(actual: Vec3, expected: Vec3) {
+    assert!(
+        actual.abs_diff_eq(expected, 1.0e-4),
+        "expected {expected}, got {actual}"
+    );
+}
+
+/// An L made of a 3×1×1 box along X and a 1×3×1 box along Y.
+fn l_shape() -> (Vec<Vec3>, Vec<[u32; 3]>) {
+    let mut vertices = Vec::new();
+    let mut triangles = Vec::new();
+    push_box(
+        &mut vertices,
+        &mut triangles,
+        Vec3::ZERO,
+        Vec3::new(3.0, 1.0, 1.0),
+    );
+    push_box(
+        &mut vertices,
+        &mut triangles,
+        Vec3::ZERO,
+        Vec3::new(1.0, 3.0, 1.0),
+    );
+
+    (vertices, triangles)
+}
+
+fn settings() -> ConvexDecompositionSettings {
+    ConvexDecompositionSettings {
+        resolution: 12,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn convex_mesh_is_one_hull() {
+    let mut vertices = Vec::new();
+    let mut triangles = Vec::new();
+    push_box(
+        &mut vertices,
+        &mut triangles,
+        Vec3::ZERO,
+        Vec3::new(2.0, 1.0, 1.0),
+    );
+
+    let decomposition = ConvexDecomposition::compute(&vertices, &triangles, &settings());
+
+    assert_eq!(decomposition.hulls.len(), 1);
+    assert_eq!(decomposition.hulls[0].len(), 8);
+
+    // The hull matches the box, without the voxels the surface touches.
+    let (min, max) = bounds(&decomposition.hulls[0]);
+    assert_near(min, Vec3::ZERO);
+    assert_near(max, Vec3::new(2.0, 1.0, 1.0));
+}
+
+#[test]
+fn l_shape_splits_into_two_hulls() {
+    let (vertices, triangles) = l_shape();
+    let decomposition = ConvexDecomposition::compute(&vertices, &triangles, &settings());
+
+    assert_eq!(decomposition.hulls.len(), 2);
+
+    // Neither hull should cover the empty corner of the L, or stick out of
+    // the mesh.
+    for hull in &decomposition.hulls {
+        let (min, max) = bounds(hull);
+        assert!(!(max.x > 2.0 && max.y > 2.0), "{min} {max}");
+        assert!(min.cmpge(Vec3::splat(-1.0e-4)).all(), "{min}");
+        assert!(max.cmple(Vec3::new(3.0, 3.0, 1.0) + 1.0e-4).all(), "{max}");
+    }
+}
+
+#[test]
+fn creates_compound_shape() {
+    global_init();
+
+    let (vertices, triangles) = l_shape();
+    let decomposition = ConvexDecomposition::compute(&vertices, &triangles, &settings());
+
+    let compound_settings = decomposition.to_static_compound_shape_settings().unwrap();
+    assert_eq!(
+        compound_settings.sub_shapes.len(),
+        decomposition.hulls.len()
+    );
+
+    let shape = decomposition.create_shape().unwrap();
+    assert_eq!(shape.sub_type(), ShapeSubType::StaticCompound);
+    assert_eq!(shape.as_compound().unwrap().num_sub_shapes(), 2);
+
+    // Hull shapes have a convex radius, so their bounds match the mesh
+    // closely but not exactly.
+    let bounds = shape.local_bounds();
+    let center_of_mass = shape.center_of_mass();
+    assert!((bounds.min + center_of_mass).abs_diff_eq(Vec3::ZERO, 0.05));
+    assert!((bounds.max + center_of_mass).abs_diff_eq(Vec3::new(3.0, 3.0, 1.0), 0.05));
+
+    let empty = ConvexDecomposition::compute(&[], &[], &settings());
+    assert!(empty.create_shape().is_err());
+}
+
+#[test]
+fn respects_limits() {
+    let mut vertices = Vec::new();
+    let mut triangles = Vec::new();
+
+    // A comb with four teeth.
+    push_box(
+        &mut vertices,
+        &mut triangles,
+        Vec3::ZERO,
+        Vec3::new(7.0, 1.0, 1.0),
+    );
+    for tooth in 0..4 {
+        let x = tooth as f32 * 2.0;
+        push_box(
+            &mut vertices,
+            &mut triangles,
+            Vec3::new(x, 0.0, 0.0),
+            Vec3::new(x + 1.0, 3.0, 1.0),
+        );
+    }
+
+    let settings = ConvexDecompositionSettings {
+        max_hulls: 3,
+        max_vertices_per_hull: 6,
+        ..settings()
+    };
+    let decomposition = ConvexDecomposition::compute(&vertices, &triangles, &settings);
+
+    assert_eq!(decomposition.hulls.len(), 3);
+    assert!(decomposition.hulls.iter().all(|hull| hull.len() <= 6));
+}
+
+#[test]
+fn empty_mesh_has_no_hulls() {
+    let decomposition = ConvexDecomposition::compute(&[], &[], &settings());
+    assert!(decomposition.hulls.is_empty());
+}
@@ -0,0 +1,67 @@
+mod framework;
+
+use rolt::{
+    OffsetCenterOfMassShapeSettings, Quat, RotatedTranslatedShapeSettings, ScaledShapeSettings,
+    ShapeError, ShapeSubType, Vec3,
+};
+
+use crate::framework::*;
+
+#[test]
+fn scaled_sphere_requires_uniform_scale() {
+    global_init();
+
+    let sphere = sphere_shape(1.0);
+    let scale = Vec3::new(1.0, 2.0, 1.0);
+    assert!(!sphere.is_valid_scale(scale));
+
+    let result = ScaledShapeSettings::new(sphere.clone(), scale).create();
+    assert_eq!(
+        result.err(),
+        Some(ShapeError::UnsupportedScale {
+            scale,
+            sub_type: ShapeSubType::Sphere,
+        })
+    );
+
+    let valid_scale = sphere.make_scale_valid(scale);
+    assert!(sphere.is_valid_scale(valid_scale));
+
+    let scaled = ScaledShapeSettings::new(sphere, valid_scale)
+        .create()
+        .unwrap();
+    assert_eq!(scaled.sub_type(), ShapeSubType::Scaled);
+}
+
+#[test]
+fn scaled_box_allows_non_uniform_scale() {
+    global_init();
+
+    let scaled = ScaledShapeSettings::new(box_shape(Vec3::ONE), Vec3::new(1.0, 2.0, 3.0))
+        .create()
+        .unwrap();
+
+    let bounds = scaled.local_bounds();
+    assert!(bounds
+        .extent()
+        .abs_diff_eq(Vec3::new(1.0, 2.0, 3.0), 1.0e-4));
+}
+
+#[test]
+fn rotated_translated_and_offset_center_of_mass() {
+    global_init();
+
+    let offset = Vec3::new(0.0, 2.0, 0.0);
+
+    let moved = RotatedTranslatedShapeSettings::new(sphere_shape(1.0), offset, Quat::IDENTITY)
+        .create()
+        .unwrap();
+    assert_eq!(moved.sub_type(), ShapeSubType::RotatedTranslated);
+    assert!(moved.center_of_mass().abs_diff_eq(offset, 1.0e-5));
+
+    let shifted = OffsetCenterOfMassShapeSettings::new(sphere_shape(1.0), offset)
+        .create()
+        .unwrap();
+    assert_eq!(shifted.sub_type(), ShapeSubType::OffsetCenterOfMass);
+    assert!(shifted.center_of_mass().abs_diff_eq(offset, 1.0e-5));
+}
@@ -0,0 +1,165 @@
+#![allow(dead_code)]
+
+use std::ffi::CStr;
+use std::ptr;
+use std::sync::Once;
+
+// Everything prefixed with `JPC_` comes from the joltc_sys crate.
+use joltc_sys::*;
+
+use rolt::{
+    BodyId, CollisionLayers, IntoJolt, ObjectLayer, PhysicsSystem, Quat, RVec3, Shape, Vec3,
+};
+
+/// Sets up Jolt once for the whole test binary.
+pub fn global_init() {
+    static INIT: Once = Once::new();
+
+    INIT.call_once(|| {
+        rolt::register_default_allocator();
+        rolt::factory_init();
+        rolt::register_types();
+    });
+}
+
+pub fn collision_layers() -> CollisionLayers {
+    CollisionLayers::builder()
+        .broad_phase_layer("non_moving")
+        .broad_phase_layer("moving")
+        .object_layer("non_moving", "non_moving")
+        .object_layer("moving", "moving")
+        .collide("non_moving", "moving")
+        .collide("moving", "moving")
+        .build()
+        .unwrap()
+}
+
+/// A physics system with the layers from [`collision_layers`], along with the
+/// allocator and job system needed to step it.
+pub struct World {
+    pub layers: CollisionLayers,
+    pub system: PhysicsSystem<'static>,
+    temp_allocator: *mut JPC_TempAllocatorImpl,
+    job_system: *mut JPC_JobSystemThreadPool,
+}
+
+impl World {
+    pub fn new() -> Self {
+        global_init();
+
+        let layers = collision_layers();
+        let system = PhysicsSystem::builder()
+            .max_bodies(64)
+            .max_body_pairs(64)
+            .max_contact_constraints(64)
+            .collision_layers(&layers)
+            .build()
+            .unwrap();
+
+        unsafe {
+            Self {
+                layers,
+                system,
+                temp_allocator: JPC_TempAllocatorImpl_new(1024 * 1024),
+                job_system: JPC_JobSystemThreadPool_new2(
+                    JPC_MAX_PHYSICS_JOBS as _,
+                    JPC_MAX_PHYSICS_BARRIERS as _,
+                ),
+            }
+        }
+    }
+
+    pub fn non_moving(&self) -> ObjectLayer {
+        self.layers.object_layer("non_moving").unwrap()
+    }
+
+    pub fn moving(&self) -> ObjectLayer {
+        self.layers.object_layer("moving").unwrap()
+    }
+
+    /// Adds a static body and returns its id.
+    pub fn add_static(&self, shape: &Shape, position: RVec3) -> BodyId {
+        self.add_body(&JPC_BodyCreationSettings {
+            Position: position.into_jolt(),
+            Rotation: Quat::IDENTITY.into_jolt(),
+            MotionType: JPC_MOTION_TYPE_STATIC,
+            ObjectLayer: self.non_moving().raw(),
+            Shape: shape.raw(),
+            ..Default::default()
+        })
+    }
+
+    /// Adds an active dynamic body and returns its id.
+    pub fn add_dynamic(&self, shape: &Shape, position: RVec3) -> BodyId {
+        self.add_body(&JPC_BodyCreationSettings {
+            Position: position.into_jolt(),
+            Rotation: Quat::IDENTITY.into_jolt(),
+            MotionType: JPC_MOTION_TYPE_DYNAMIC,
+            ObjectLayer: self.moving().raw(),
+            Shape: shape.raw(),
+            ..Default::default()
+        })
+    }
+
+    fn add_body(&self, settings: &JPC_BodyCreationSettings) -> BodyId {
+        let body_interface = self.system.body_interface();
+        let body = unsafe { body_interface.create_body(settings) }.unwrap();
+        let body_id = body.id();
+
+        body_interface.add_body(body_id, JPC_ACTIVATION_ACTIVATE);
+        body_id
+    }
+
+    /// Steps the simulation by one 60 Hz frame.
+    pub fn step(&mut self) {
+        unsafe {
+            self.system
+                .update(1.0 / 60.0, 1, self.temp_allocator, self.job_system)
+                .unwrap();
+        }
+    }
+
+    pub fn temp_allocator(&self) -> *mut JPC_TempAllocatorImpl {
+        self.temp_allocator
+    }
+}
+
+impl Drop for World {
+    fn drop(&mut self) {
+        unsafe {
+            JPC_JobSystemThreadPool_delete(self.job_system);
+            JPC_TempAllocatorImpl_delete(self.temp_allocator);
+        }
+    }
+}
+
+pub fn box_shape(half_extent: Vec3) -> Shape {
+    let settings = JPC_BoxShapeSettings {
+        HalfExtent: half_extent.into_jolt(),
+        ..Default::default()
+    };
+
+    create_shape(|shape, err| unsafe { JPC_BoxShapeSettings_Create(&settings, shape, err) })
+}
+
+pub fn sphere_shape(radius: f32) -> Shape {
+    let settings = JPC_SphereShapeSettings {
+        Radius: radius,
+        ..Default::default()
+    };
+
+    create_shape(|shape, err| unsafe { JPC_SphereShapeSettings_Create(&settings, shape, err) })
+}
+
+fn create_shape(create: impl FnOnce(*mut *mut JPC_Shape, *mut *mut JPC_String) -> bool) -> Shape {
+    let mut shape: *mut JPC_Shape = ptr::null_mut();
+    let mut err: *mut JPC_String = ptr::null_mut();
+
+    unsafe {
+        if create(&mut shape, &mut err) {
+            Shape::from_raw(shape)
+        } else {
+            panic!("{:?}", CStr::from_ptr(JPC_String_c_str(err)));
+        }
+    }
+}
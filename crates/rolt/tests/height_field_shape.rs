@@ -0,0 +1,178 @@
+mod framework;
+
+use rolt::{
+    HeightFieldRegion, HeightFieldShape, HeightFieldShapeSettings, RRayCast, RVec3, RayCastArgs,
+    ShapeError, Vec3,
+};
+
+use crate::framework::*;
+
+const SAMPLE_COUNT: u32 = 8;
+
+fn sloped_samples() -> Vec<f32> {
+    let mut samples = Vec::new();
+
+    for y in 0..SAMPLE_COUNT {
+        for x in 0..SAMPLE_COUNT {
+            samples.push((x + y) as f32 * 0.1);
+        }
+    }
+
+    samples
+}
+
+#[test]
+fn sample_positions_and_holes() {
+    global_init();
+
+    let mut samples = sloped_samples();
+    samples[(7 * SAMPLE_COUNT + 7) as usize] = HeightFieldShape::NO_COLLISION_VALUE;
+
+    let mut settings = HeightFieldShapeSettings::new(&samples, SAMPLE_COUNT);
+    settings.offset = Vec3::new(-4.0, 0.0, -4.0);
+    settings.scale = Vec3::new(2.0, 1.0, 2.0);
+
+    let shape = settings.create().unwrap();
+    let height_field = shape.as_height_field().unwrap();
+    assert_eq!(height_field.sample_count(), SAMPLE_COUNT);
+
+    let position = height_field.get_position(3, 4);
+    assert!(position.abs_diff_eq(Vec3::new(2.0, 0.7, 4.0), 0.01));
+
+    assert!(height_field.is_no_collision(7, 7));
+    assert!(!height_field.is_no_collision(6, 7));
+}
+
+#[test]
+fn create_checks_sample_count() {
+    let samples = sloped_samples();
+    let result = HeightFieldShapeSettings::new(&samples[1..], SAMPLE_COUNT).create();
+
+    assert!(matches!(result, Err(ShapeError::Invalid(_))));
+}
+
+#[test]
+fn heights_round_trip() {
+    let world = World::new();
+
+    let mut samples = sloped_samples();
+    samples[(7 * SAMPLE_COUNT + 7) as usize] = HeightFieldShape::NO_COLLISION_VALUE;
+
+    let shape = HeightFieldShapeSettings::new(&samples, SAMPLE_COUNT)
+        .create()
+        .unwrap();
+    let height_field = shape.as_height_field().unwrap();
+
+    let region = HeightFieldRegion::new(0, 0, SAMPLE_COUNT, SAMPLE_COUNT);
+    let heights = height_field.get_heights(region);
+    assert_eq!(heights[63], HeightFieldShape::NO_COLLISION_VALUE);
+
+    for (height, sample) in heights.iter().zip(&samples).take(63) {
+        assert!((height - sample).abs() < 0.01, "{height} != {sample}");
+    }
+
+    let block_size = height_field.block_size();
+    let region = HeightFieldRegion::new(block_size, block_size, block_size, block_size);
+    let new_heights = vec![0.5; region.len()];
+
+    unsafe {
+        height_field.set_heights(region, &new_heights, world.temp_allocator());
+    }
+
+    for height in height_field.get_heights(region) {
+        assert!((height - 0.5).abs() < 0.01, "{height} != 0.5");
+    }
+
+    let position = height_field.get_position(block_size, block_size);
+    assert!((position.y - 0.5).abs() < 0.01);
+}
+
+#[test]
+#[should_panic(expected = "not aligned to the block size")]
+fn get_heights_checks_alignment() {
+    global_init();
+
+    let samples = sloped_samples();
+    let shape = HeightFieldShapeSettings::new(&samples, SAMPLE_COUNT)
+        .create()
+        .unwrap();
+    let height_field = shape.as_height_field().unwrap();
+
+    height_field.get_heights(HeightFieldRegion::new(1, 0, 1, 1));
+}
+
+#[test]
+fn set_height_field_heights_wakes_resting_bodies() {
+    let mut world = World::new();
+
+    let samples = vec![0.0; (SAMPLE_COUNT * SAMPLE_COUNT) as usize];
+    let terrain = HeightFieldShapeSettings::new(&samples, SAMPLE_COUNT)
+        .create()
+        .unwrap();
+    let terrain_id = world.add_static(&terrain, RVec3::ZERO);
+
+    let sphere_id = world.add_dynamic(&sphere_shape(0.5), RVec3::new(3.5, 0.5, 3.5));
+
+    for _ in 0..300 {
+        if !world.system.body_interface().is_active(sphere_id) {
+            break;
+        }
+
+        world.step();
+    }
+
+    let body_interface = world.system.body_interface();
+    assert!(!body_interface.is_active(sphere_id), "sphere never slept");
+
+    // Lower the terrain under the sphere.
+    let block_size = terrain.as_height_field().unwrap().block_size();
+    let region = HeightFieldRegion::new(0, 0, 2 * block_size, 2 * block_size);
+    let heights = vec![-1.0; region.len()];
+
+    unsafe {
+        body_interface
+            .set_height_field_heights(terrain_id, region, &heights, world.temp_allocator())
+            .unwrap();
+    }
+
+    assert!(body_interface.is_active(sphere_id));
+}
+
+#[test]
+fn set_height_field_heights_updates_broad_phase_bounds() {
+    let world = World::new();
+
+    let samples = vec![0.0; (SAMPLE_COUNT * SAMPLE_COUNT) as usize];
+    let terrain = HeightFieldShapeSettings::new(&samples, SAMPLE_COUNT)
+        .create()
+        .unwrap();
+    let terrain_id = world.add_static(&terrain, RVec3::ZERO);
+
+    // Stops at a height of 2, above the bounds of the flat terrain.
+    let ray = || RayCastArgs {
+        ray: RRayCast {
+            origin: RVec3::new(1.5, 10.0, 1.5),
+            direction: Vec3::new(0.0, -8.0, 0.0),
+        },
+        ..Default::default()
+    };
+    let narrow_phase = world.system.narrow_phase_query();
+    assert!(narrow_phase.cast_ray(ray()).is_none());
+
+    // Raise the terrain under the ray to a height of 3.
+    let block_size = terrain.as_height_field().unwrap().block_size();
+    let region = HeightFieldRegion::new(0, 0, 2 * block_size, 2 * block_size);
+    let heights = vec![3.0; region.len()];
+
+    unsafe {
+        world
+            .system
+            .body_interface()
+            .set_height_field_heights(terrain_id, region, &heights, world.temp_allocator())
+            .unwrap();
+    }
+
+    let hit = narrow_phase.cast_ray(ray()).unwrap();
+    assert_eq!(hit.body_id, terrain_id);
+    assert!((hit.fraction - 7.0 / 8.0).abs() < 1.0e-3);
+}
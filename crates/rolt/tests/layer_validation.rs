@@ -0,0 +1,149 @@
+mod framework;
+
+use rolt::{
+    validate_layers, BroadPhaseLayer, BroadPhaseLayerInterface, CollisionLayers,
+    LayerValidationError, ObjectLayer, ObjectLayerPairFilter, ObjectVsBroadPhaseLayerFilter,
+    PhysicsSystem, PhysicsSystemError,
+};
+
+use crate::framework::*;
+
+struct BroadPhaseLayers {
+    num_layers: u32,
+}
+
+impl BroadPhaseLayerInterface for BroadPhaseLayers {
+    fn get_num_broad_phase_layers(&self) -> u32 {
+        self.num_layers
+    }
+
+    fn get_broad_phase_layer(&self, layer: ObjectLayer) -> BroadPhaseLayer {
+        match layer.raw() {
+            0 => BroadPhaseLayer::new(0),
+            1 => BroadPhaseLayer::new(1),
+            _ => unreachable!(),
+        }
+    }
+}
+
+struct AllBroadPhaseLayers;
+
+impl ObjectVsBroadPhaseLayerFilter for AllBroadPhaseLayers {
+    fn should_collide(&self, _layer1: ObjectLayer, _layer2: BroadPhaseLayer) -> bool {
+        true
+    }
+}
+
+/// Layer 0 collides with layer 1, but not the other way around.
+struct OneWayPairs;
+
+impl ObjectLayerPairFilter for OneWayPairs {
+    fn should_collide(&self, layer1: ObjectLayer, layer2: ObjectLayer) -> bool {
+        layer1.raw() == 0 && layer2.raw() == 1
+    }
+}
+
+/// Borrows another pair filter, so it isn't `'static`.
+struct BorrowedPairs<'a>(&'a OneWayPairs);
+
+impl ObjectLayerPairFilter for BorrowedPairs<'_> {
+    fn should_collide(&self, layer1: ObjectLayer, layer2: ObjectLayer) -> bool {
+        self.0.should_collide(layer1, layer2)
+    }
+}
+
+#[test]
+fn finds_out_of_range_broad_phase_layer() {
+    let result = validate_layers(
+        2,
+        &BroadPhaseLayers { num_layers: 1 },
+        &AllBroadPhaseLayers,
+        &OneWayPairs,
+    );
+
+    assert_eq!(
+        result,
+        Err(LayerValidationError::BroadPhaseLayerOutOfRange {
+            object_layer: ObjectLayer::new(1),
+            broad_phase_layer: BroadPhaseLayer::new(1),
+            num_broad_phase_layers: 1,
+        })
+    );
+}
+
+#[test]
+fn finds_asymmetric_pair_filter() {
+    let result = validate_layers(
+        2,
+        &BroadPhaseLayers { num_layers: 2 },
+        &AllBroadPhaseLayers,
+        &OneWayPairs,
+    );
+
+    assert_eq!(
+        result,
+        Err(LayerValidationError::AsymmetricPairFilter {
+            layer1: ObjectLayer::new(0),
+            layer2: ObjectLayer::new(1),
+        })
+    );
+}
+
+#[test]
+fn catches_panics() {
+    let result = validate_layers(
+        3,
+        &BroadPhaseLayers { num_layers: 2 },
+        &AllBroadPhaseLayers,
+        &OneWayPairs,
+    );
+
+    assert!(matches!(
+        result,
+        Err(LayerValidationError::Panicked {
+            method: "get_broad_phase_layer",
+            object_layer: Some(layer),
+            ..
+        }) if layer == ObjectLayer::new(2)
+    ));
+}
+
+#[test]
+fn accepts_collision_layers() {
+    let layers = CollisionLayers::builder()
+        .broad_phase_layer("non_moving")
+        .broad_phase_layer("moving")
+        .object_layer("non_moving", "non_moving")
+        .object_layer("moving", "moving")
+        .collide("non_moving", "moving")
+        .collide("moving", "moving")
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        validate_layers(layers.num_object_layers(), &layers, &layers, &layers),
+        Ok(())
+    );
+}
+
+#[test]
+fn builder_validates_borrowed_layers() {
+    global_init();
+
+    let pairs = OneWayPairs;
+    let result = PhysicsSystem::builder()
+        .validated_layers(
+            2,
+            BroadPhaseLayers { num_layers: 2 },
+            AllBroadPhaseLayers,
+            BorrowedPairs(&pairs),
+        )
+        .build();
+
+    assert!(matches!(
+        result,
+        Err(PhysicsSystemError::InvalidLayers(
+            LayerValidationError::AsymmetricPairFilter { .. }
+        ))
+    ));
+}
@@ -0,0 +1,135 @@
+#![cfg(feature = "asserts")]
+
+mod framework;
+
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use joltc_sys::JPC_ACTIVATION_DONT_ACTIVATE;
+use rolt::{AssertAction, AssertFailure, BodyId, Quat, RVec3, Vec3};
+
+use crate::framework::*;
+
+/// The assert handler is shared by the whole process, so tests that set one
+/// must not run at the same time.
+fn serialize() -> MutexGuard<'static, ()> {
+    static LOCK: Mutex<()> = Mutex::new(());
+
+    LOCK.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// An assertion failure copied out of the handler.
+#[derive(Debug)]
+struct Failure {
+    expression: String,
+    file: String,
+    line: u32,
+}
+
+impl Failure {
+    fn new(failure: &AssertFailure<'_>) -> Self {
+        Self {
+            expression: failure.expression.to_owned(),
+            file: failure.file.to_owned(),
+            line: failure.line,
+        }
+    }
+}
+
+/// Rotates a body by a quaternion that isn't normalized, which fails Jolt's
+/// `IsNormalized()` assertion in a checked `BodyInterface` call.
+fn rotate_by_unnormalized_quat(world: &World, body_id: BodyId) {
+    world
+        .system
+        .body_interface()
+        .set_position_and_rotation_when_changed(
+            body_id,
+            RVec3::ZERO,
+            Quat::from_xyzw(0.0, 0.0, 0.0, 2.0),
+            JPC_ACTIVATION_DONT_ACTIVATE,
+        );
+}
+
+fn world_with_body() -> (World, BodyId) {
+    rolt::register_log_handlers();
+
+    let world = World::new();
+    let body_id = world.add_dynamic(&box_shape(Vec3::splat(0.5)), RVec3::ZERO);
+
+    (world, body_id)
+}
+
+#[test]
+fn assert_failure_display() {
+    let failure = AssertFailure {
+        expression: "inValue > 0",
+        message: None,
+        file: "Jolt/Core/Foo.cpp",
+        line: 12,
+    };
+    assert_eq!(
+        failure.to_string(),
+        "Jolt/Core/Foo.cpp:12: assertion `inValue > 0` failed"
+    );
+
+    let failure = AssertFailure {
+        message: Some("value must be positive"),
+        ..failure
+    };
+    assert_eq!(
+        failure.to_string(),
+        "Jolt/Core/Foo.cpp:12: assertion `inValue > 0` failed: value must be positive"
+    );
+}
+
+#[test]
+fn continue_reports_failure_to_handler() {
+    let _guard = serialize();
+    let (world, body_id) = world_with_body();
+
+    let failures = Arc::new(Mutex::new(Vec::new()));
+    rolt::set_assert_handler({
+        let failures = Arc::clone(&failures);
+        move |failure| {
+            failures.lock().unwrap().push(Failure::new(failure));
+            AssertAction::Continue
+        }
+    });
+
+    rotate_by_unnormalized_quat(&world, body_id);
+
+    let failures = failures.lock().unwrap();
+    let failure = failures.first().expect("no assertion failed");
+    assert!(failure.expression.contains("IsNormalized"), "{failure:?}");
+    assert!(!failure.file.is_empty(), "{failure:?}");
+    assert!(failure.line > 0, "{failure:?}");
+}
+
+#[test]
+fn panic_surfaces_at_checked_call() {
+    let _guard = serialize();
+    let (world, body_id) = world_with_body();
+
+    rolt::set_assert_handler(|_| AssertAction::Panic);
+
+    let payload = catch_unwind(AssertUnwindSafe(|| {
+        rotate_by_unnormalized_quat(&world, body_id);
+    }))
+    .unwrap_err();
+
+    let message = payload.downcast_ref::<String>().unwrap();
+    assert!(message.contains("IsNormalized"), "{message}");
+}
+
+#[test]
+fn handler_can_replace_itself() {
+    let _guard = serialize();
+    let (world, body_id) = world_with_body();
+
+    rolt::set_assert_handler(|_| {
+        rolt::set_assert_handler(|_| AssertAction::Continue);
+        AssertAction::Continue
+    });
+
+    rotate_by_unnormalized_quat(&world, body_id);
+}
@@ -0,0 +1,113 @@
+#![cfg(feature = "mesh-import")]
+
+use std::path::PathBuf;
+
+use rolt::{Color, ImportedMesh, MeshImportError, PhysicsMaterial, PhysicsMaterialList, Vec3};
+
+fn fixture(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(name)
+}
+
+fn material_of(mesh: &ImportedMesh, triangle: usize) -> &str {
+    &mesh.material_names[mesh.triangles[triangle].material_index as usize]
+}
+
+#[test]
+fn obj_with_materials() {
+    let mesh = ImportedMesh::load(fixture("ramp.obj")).unwrap();
+
+    assert_eq!(mesh.triangles.len(), 2);
+    assert_eq!(material_of(&mesh, 0), "grass");
+    assert_eq!(material_of(&mesh, 1), "rock");
+
+    for triangle in &mesh.triangles {
+        assert!(triangle
+            .indices
+            .iter()
+            .all(|&index| (index as usize) < mesh.vertices.len()));
+    }
+}
+
+#[test]
+fn gltf_applies_node_transform() {
+    for name in ["triangle.gltf", "triangle.glb"] {
+        let mesh = ImportedMesh::load(fixture(name)).unwrap();
+
+        assert_eq!(mesh.triangles.len(), 1, "{name}");
+        assert_eq!(material_of(&mesh, 0), "metal", "{name}");
+
+        let [a, b, c] = mesh.triangles[0]
+            .indices
+            .map(|index| mesh.vertices[index as usize]);
+        assert_eq!(a, Vec3::new(0.0, 5.0, 0.0), "{name}");
+        assert_eq!(b, Vec3::new(1.0, 5.0, 0.0), "{name}");
+        assert_eq!(c, Vec3::new(0.0, 5.0, 1.0), "{name}");
+    }
+}
+
+fn triangle_normal(mesh: &ImportedMesh, triangle: usize) -> Vec3 {
+    let [a, b, c] = mesh.triangles[triangle]
+        .indices
+        .map(|index| mesh.vertices[index as usize]);
+
+    (b - a).cross(c - a).normalize()
+}
+
+#[test]
+fn gltf_mirrored_node_keeps_winding() {
+    let mesh = ImportedMesh::load(fixture("triangle.gltf")).unwrap();
+    let mirrored = ImportedMesh::load(fixture("mirrored.gltf")).unwrap();
+
+    assert_eq!(mirrored.triangles.len(), 1);
+    assert!(mirrored.vertices.contains(&Vec3::new(-1.0, 5.0, 0.0)));
+
+    // Mirroring along X doesn't change which way a triangle in the XZ plane
+    // faces.
+    assert_eq!(triangle_normal(&mesh, 0), Vec3::NEG_Y);
+    assert_eq!(triangle_normal(&mirrored, 0), Vec3::NEG_Y);
+}
+
+#[test]
+fn mesh_shape_settings_map_materials() {
+    let mesh = ImportedMesh::load(fixture("ramp.obj")).unwrap();
+
+    let mut materials = PhysicsMaterialList::new();
+    materials.push(PhysicsMaterial::new(
+        "rock",
+        Color::new(128, 128, 128, 255),
+        (),
+    ));
+    materials.push(PhysicsMaterial::new(
+        "grass",
+        Color::new(0, 255, 0, 255),
+        (),
+    ));
+
+    let settings = mesh.to_mesh_shape_settings(&materials).unwrap();
+    assert_eq!(settings.triangles[0].material_index, 1);
+    assert_eq!(settings.triangles[1].material_index, 0);
+    assert_eq!(settings.materials.len(), 2);
+
+    let settings = mesh
+        .to_mesh_shape_settings(&PhysicsMaterialList::<()>::new())
+        .unwrap();
+    assert!(settings.materials.is_empty());
+
+    let mut materials = PhysicsMaterialList::new();
+    materials.push(PhysicsMaterial::new(
+        "grass",
+        Color::new(0, 255, 0, 255),
+        (),
+    ));
+
+    let err = mesh.to_mesh_shape_settings(&materials).unwrap_err();
+    assert!(matches!(err, MeshImportError::UnknownMaterial(name) if name == "rock"));
+}
+
+#[test]
+fn unsupported_extension() {
+    let err = ImportedMesh::load(fixture("ramp.mtl")).unwrap_err();
+    assert!(matches!(err, MeshImportError::UnsupportedFormat(_)));
+}
@@ -0,0 +1,254 @@
+mod framework;
+
+use joltc_sys::JPC_ShapeCastResult;
+use rolt::{
+    AABox, AllHitCollidePointCollector, AllHitCollideShapeCollector, CastShapeBase,
+    CastShapeCollector, CollectFacesMode, CollectTransformedShapesArgs, CollidePointArgs,
+    CollidePointCollectorImpl, CollideShapeQuery, FromJolt, Quat, RVec3, ShapeCastHit,
+    ShapeCastQuery, ShapeSubType, StaticCompoundShapeSettings, SubShapeId, SubShapeSettings, Vec3,
+};
+
+use crate::framework::*;
+
+/// A static compound of two unit boxes, two meters apart along X.
+fn two_boxes() -> rolt::Shape {
+    let unit_box = box_shape(Vec3::splat(0.5));
+
+    StaticCompoundShapeSettings::new(vec![
+        SubShapeSettings::new(unit_box.clone(), Vec3::new(-1.0, 0.0, 0.0), Quat::IDENTITY),
+        SubShapeSettings::new(unit_box, Vec3::new(1.0, 0.0, 0.0), Quat::IDENTITY),
+    ])
+    .create()
+    .unwrap()
+}
+
+#[test]
+fn collide_point_finds_body_and_sub_shape() {
+    let world = World::new();
+    let shape = two_boxes();
+    let body_id = world.add_static(&shape, RVec3::new(0.0, 5.0, 0.0));
+    let narrow_phase = world.system.narrow_phase_query();
+
+    let mut collector = AllHitCollidePointCollector::new();
+    narrow_phase.collide_point(CollidePointArgs {
+        point: RVec3::new(1.25, 5.0, 0.0),
+        collector: CollidePointCollectorImpl::new_borrowed(&mut collector),
+        broad_phase_layer_filter: None,
+        object_layer_filter: None,
+        body_filter: None,
+        shape_filter: None,
+    });
+
+    assert_eq!(collector.result.len(), 1);
+    let hit = collector.result[0];
+    assert_eq!(hit.body_id, body_id);
+
+    let compound = shape.as_compound().unwrap();
+    let (child_index, _) = compound.child_index_for(hit.sub_shape_id).unwrap();
+    assert_eq!(child_index, 1);
+
+    let mut collector = AllHitCollidePointCollector::new();
+    narrow_phase.collide_point(CollidePointArgs {
+        point: RVec3::new(0.0, 5.0, 0.0),
+        collector: CollidePointCollectorImpl::new_borrowed(&mut collector),
+        broad_phase_layer_filter: None,
+        object_layer_filter: None,
+        body_filter: None,
+        shape_filter: None,
+    });
+
+    assert!(collector.result.is_empty());
+}
+
+#[test]
+fn collect_transformed_shapes_returns_leaf_shapes() {
+    let world = World::new();
+    let body_id = world.add_static(&two_boxes(), RVec3::new(0.0, 5.0, 0.0));
+    let narrow_phase = world.system.narrow_phase_query();
+
+    let shapes = narrow_phase.collect_transformed_shapes(CollectTransformedShapesArgs {
+        bounds: AABox::from_center_half_extent(Vec3::new(-1.0, 5.0, 0.0), Vec3::splat(0.25)),
+        ..Default::default()
+    });
+
+    assert_eq!(shapes.len(), 1);
+    let shape = &shapes[0];
+    assert_eq!(shape.body_id, body_id);
+    assert_eq!(shape.shape.sub_type(), ShapeSubType::Box);
+    assert!(shape.position.distance(RVec3::new(-1.0, 5.0, 0.0)) < 1.0e-5);
+    assert!(shape.rotation.abs_diff_eq(Quat::IDENTITY, 1.0e-6));
+    assert_eq!(shape.scale, Vec3::ONE);
+
+    let shapes = narrow_phase.collect_transformed_shapes(CollectTransformedShapesArgs {
+        bounds: AABox::from_center_half_extent(Vec3::new(0.0, 5.0, 0.0), Vec3::splat(2.0)),
+        ..Default::default()
+    });
+
+    assert_eq!(shapes.len(), 2);
+    assert!(shapes.iter().all(|shape| shape.body_id == body_id));
+}
+
+/// Keeps every hit instead of only the closest one.
+#[derive(Default)]
+struct AllHitCastShapeCollector {
+    hits: Vec<ShapeCastHit>,
+}
+
+impl CastShapeCollector for AllHitCastShapeCollector {
+    fn reset(&mut self) {
+        self.hits.clear();
+    }
+
+    fn add_hit(&mut self, _base: &mut CastShapeBase, result: &JPC_ShapeCastResult) {
+        self.hits.push(ShapeCastHit::from_jolt(*result));
+    }
+}
+
+#[test]
+fn cast_shape_with_reports_every_hit() {
+    let world = World::new();
+    let shape = two_boxes();
+    let body_id = world.add_static(&shape, RVec3::new(0.0, 5.0, 0.0));
+    let narrow_phase = world.system.narrow_phase_query();
+    let compound = shape.as_compound().unwrap();
+    let sphere = sphere_shape(0.25);
+
+    let mut collector = AllHitCastShapeCollector::default();
+    narrow_phase.cast_shape_with(
+        ShapeCastQuery::new(
+            &sphere,
+            RVec3::new(-5.0, 5.0, 0.0),
+            Quat::IDENTITY,
+            Vec3::new(10.0, 0.0, 0.0),
+        ),
+        &mut collector,
+    );
+
+    let mut children: Vec<u32> = collector
+        .hits
+        .iter()
+        .map(|hit| {
+            assert_eq!(hit.body_id, body_id);
+            compound.child_index_for(hit.sub_shape_id_2).unwrap().0
+        })
+        .collect();
+    children.sort();
+    assert_eq!(children, [0, 1]);
+
+    let closest = narrow_phase
+        .cast_shape_closest(ShapeCastQuery::new(
+            &sphere,
+            RVec3::new(-5.0, 5.0, 0.0),
+            Quat::IDENTITY,
+            Vec3::new(10.0, 0.0, 0.0),
+        ))
+        .unwrap();
+    assert_eq!(
+        compound.child_index_for(closest.sub_shape_id_2).unwrap().0,
+        0
+    );
+
+    let miss = narrow_phase.cast_shape_closest(ShapeCastQuery::new(
+        &sphere,
+        RVec3::new(-5.0, 0.0, 0.0),
+        Quat::IDENTITY,
+        Vec3::new(10.0, 0.0, 0.0),
+    ));
+    assert!(miss.is_none());
+}
+
+#[test]
+fn collide_shape_with_uses_settings() {
+    let world = World::new();
+    let shape = two_boxes();
+    let body_id = world.add_static(&shape, RVec3::new(0.0, 5.0, 0.0));
+    let narrow_phase = world.system.narrow_phase_query();
+    let sphere = sphere_shape(0.5);
+
+    // Resting 0.1 above the top of the second box.
+    let position = RVec3::new(1.0, 6.1, 0.0);
+
+    let hits =
+        narrow_phase.collide_shape_all(CollideShapeQuery::new(&sphere, position, Quat::IDENTITY));
+    assert!(hits.is_empty());
+
+    let mut query = CollideShapeQuery::new(&sphere, position, Quat::IDENTITY);
+    query.settings.max_separation_distance = 0.2;
+
+    let mut collector = AllHitCollideShapeCollector::new();
+    narrow_phase.collide_shape_with(query, &mut collector);
+
+    assert_eq!(collector.result.len(), 1);
+    let hit = &collector.result[0];
+    assert_eq!(hit.body_id, body_id);
+    let compound = shape.as_compound().unwrap();
+    assert_eq!(compound.child_index_for(hit.sub_shape_id_2).unwrap().0, 1);
+}
+
+fn assert_near(actual: Vec3, expected: Vec3) {
+    assert!(
+        actual.abs_diff_eq(expected, 1.0e-3),
+        "expected {expected}, got {actual}"
+    );
+}
+
+#[test]
+fn shape_cast_hit_fields() {
+    let world = World::new();
+    let body_id = world.add_static(&box_shape(Vec3::ONE), RVec3::new(0.0, 5.0, 0.0));
+    let narrow_phase = world.system.narrow_phase_query();
+    let sphere = sphere_shape(0.5);
+
+    let mut query = ShapeCastQuery::new(
+        &sphere,
+        RVec3::new(-5.0, 5.0, 0.0),
+        Quat::IDENTITY,
+        Vec3::new(10.0, 0.0, 0.0),
+    );
+    query.base_offset = RVec3::new(0.0, 5.0, 0.0);
+
+    let hit = narrow_phase.cast_shape_closest(query).unwrap();
+
+    // The sphere touches the box after moving 3.5 of the 10 units.
+    assert!((hit.fraction - 0.35).abs() < 1.0e-3);
+    assert_eq!(hit.early_out_fraction(), hit.fraction);
+    assert_eq!(hit.body_id, body_id);
+    assert_eq!(hit.sub_shape_id_1, SubShapeId::EMPTY);
+    assert_eq!(hit.sub_shape_id_2, SubShapeId::EMPTY);
+    assert!(!hit.is_back_face_hit);
+
+    // Contact points are relative to the base offset.
+    assert_near(hit.contact_point_on_1, Vec3::new(-1.0, 0.0, 0.0));
+    assert_near(hit.contact_point_on_2, Vec3::new(-1.0, 0.0, 0.0));
+    assert_near(hit.penetration_axis.normalize(), Vec3::X);
+    assert!(hit.shape_1_face.is_none());
+    assert!(hit.shape_2_face.is_none());
+}
+
+#[test]
+fn collide_shape_hit_fields() {
+    let world = World::new();
+    let body_id = world.add_static(&box_shape(Vec3::ONE), RVec3::new(0.0, 5.0, 0.0));
+    let narrow_phase = world.system.narrow_phase_query();
+    let sphere = sphere_shape(0.5);
+
+    let mut query = CollideShapeQuery::new(&sphere, RVec3::new(-1.25, 5.0, 0.0), Quat::IDENTITY);
+    query.base_offset = RVec3::new(0.0, 5.0, 0.0);
+    query.settings.collect_faces_mode = CollectFacesMode::CollectFaces;
+
+    let hits = narrow_phase.collide_shape_all(query);
+    assert_eq!(hits.len(), 1);
+    let hit = &hits[0];
+
+    assert_eq!(hit.body_id, body_id);
+    assert_eq!(hit.sub_shape_id_2, SubShapeId::EMPTY);
+    assert!((hit.penetration_depth - 0.25).abs() < 1.0e-3);
+    assert_near(hit.penetration_axis.normalize(), Vec3::X);
+    assert_near(hit.contact_point_on_1, Vec3::new(-0.75, 0.0, 0.0));
+    assert_near(hit.contact_point_on_2, Vec3::new(-1.0, 0.0, 0.0));
+
+    // The face of the box that the sphere is pushed into.
+    let face = hit.shape_2_face.as_ref().unwrap();
+    assert_eq!(face.len(), 4);
+    assert!(face.iter().all(|point| (point.x + 1.0).abs() < 1.0e-3));
+}
@@ -0,0 +1,75 @@
+#![cfg(feature = "object-layer-u32")]
+
+use rolt::{
+    BroadPhaseLayer, BroadPhaseLayerInterface, BroadPhaseLayerInterfaceMask, ObjectLayer,
+    ObjectLayerPairFilterMask, ObjectVsBroadPhaseLayerFilter,
+};
+
+const STATIC: u16 = 1 << 0;
+const PLAYER: u16 = 1 << 1;
+const DEBRIS: u16 = 1 << 2;
+
+#[test]
+fn group_and_mask_round_trip() {
+    let layer = ObjectLayer::from_group_and_mask(PLAYER, STATIC | DEBRIS);
+
+    assert_eq!(layer.group(), PLAYER);
+    assert_eq!(layer.mask(), STATIC | DEBRIS);
+    assert_eq!(ObjectLayer::from_group(DEBRIS).mask(), u16::MAX);
+}
+
+#[test]
+fn layers_collide_when_both_masks_match() {
+    let player = ObjectLayer::from_group_and_mask(PLAYER, STATIC | PLAYER);
+    let debris = ObjectLayer::from_group_and_mask(DEBRIS, STATIC);
+    let ground = ObjectLayer::from_group(STATIC);
+
+    assert!(ObjectLayerPairFilterMask::layers_collide(player, ground));
+    assert!(ObjectLayerPairFilterMask::layers_collide(player, player));
+    assert!(ObjectLayerPairFilterMask::layers_collide(debris, ground));
+    assert!(!ObjectLayerPairFilterMask::layers_collide(player, debris));
+}
+
+#[test]
+fn broad_phase_layers_are_picked_by_group() {
+    let non_moving = BroadPhaseLayer::new(0);
+    let moving = BroadPhaseLayer::new(1);
+
+    let mut interface = BroadPhaseLayerInterfaceMask::new(3);
+    interface.configure_layer(non_moving, STATIC, 0);
+    interface.configure_layer(moving, PLAYER | DEBRIS, 0);
+    let filter = interface.object_vs_broad_phase_layer_filter();
+
+    let debris = ObjectLayer::from_group_and_mask(DEBRIS, STATIC);
+    assert_eq!(interface.get_num_broad_phase_layers(), 3);
+    assert_eq!(interface.get_broad_phase_layer(debris), moving);
+    assert_eq!(
+        interface.get_broad_phase_layer(ObjectLayer::from_group(STATIC)),
+        non_moving
+    );
+
+    assert!(filter.should_collide(debris, non_moving));
+    assert!(!filter.should_collide(debris, moving));
+}
+
+#[test]
+fn unmapped_groups_use_the_last_layer() {
+    const UNMAPPED: u16 = 1 << 3;
+
+    let non_moving = BroadPhaseLayer::new(0);
+    let fallback = BroadPhaseLayer::new(1);
+
+    let mut interface = BroadPhaseLayerInterfaceMask::new(2);
+    interface.configure_layer(non_moving, STATIC, 0);
+    let filter = interface.object_vs_broad_phase_layer_filter();
+
+    let unmapped = ObjectLayer::from_group_and_mask(UNMAPPED, STATIC);
+    assert_eq!(interface.get_broad_phase_layer(unmapped), fallback);
+
+    // The fallback layer collides with everything, even objects whose mask
+    // has none of its groups.
+    let debris = ObjectLayer::from_group_and_mask(DEBRIS, 0);
+    assert!(filter.should_collide(debris, fallback));
+    assert!(!filter.should_collide(debris, non_moving));
+    assert!(filter.should_collide(unmapped, non_moving));
+}
@@ -0,0 +1,139 @@
+mod framework;
+
+use std::thread;
+
+use joltc_sys::*;
+use rolt::{
+    Body, BodyFilter, BodyFilterImpl, BodyId, BroadPhaseLayer, BroadPhaseLayerInterface,
+    CollisionLayers, IntoJolt, ObjectLayer, PhysicsSystem, Quat, RRayCast, RVec3, RayCastArgs,
+    Vec3,
+};
+
+use crate::framework::*;
+
+struct PanickingBroadPhaseLayers;
+
+impl BroadPhaseLayerInterface for PanickingBroadPhaseLayers {
+    fn get_num_broad_phase_layers(&self) -> u32 {
+        panic!("broad phase layers are broken");
+    }
+
+    fn get_broad_phase_layer(&self, _layer: ObjectLayer) -> BroadPhaseLayer {
+        BroadPhaseLayer::new(0)
+    }
+}
+
+#[test]
+#[should_panic(expected = "broad phase layers are broken")]
+fn panics_in_callbacks_are_resumed() {
+    global_init();
+
+    let layers = CollisionLayers::builder()
+        .broad_phase_layer("default")
+        .object_layer("default", "default")
+        .collide("default", "default")
+        .build()
+        .unwrap();
+
+    let _ = PhysicsSystem::builder()
+        .layers(PanickingBroadPhaseLayers, layers.clone(), layers)
+        .build();
+}
+
+/// Only knows the broad phase layer of object layer 0.
+struct FirstLayerOnly;
+
+impl BroadPhaseLayerInterface for FirstLayerOnly {
+    fn get_num_broad_phase_layers(&self) -> u32 {
+        1
+    }
+
+    fn get_broad_phase_layer(&self, layer: ObjectLayer) -> BroadPhaseLayer {
+        assert_eq!(layer.raw(), 0, "object layer is not mapped");
+        BroadPhaseLayer::new(0)
+    }
+}
+
+#[test]
+#[should_panic(expected = "object layer is not mapped")]
+fn panics_while_adding_bodies_are_resumed() {
+    global_init();
+
+    let layers = CollisionLayers::builder()
+        .broad_phase_layer("default")
+        .object_layer("first", "default")
+        .object_layer("second", "default")
+        .collide("first", "second")
+        .build()
+        .unwrap();
+    let second = layers.object_layer("second").unwrap();
+
+    let system = PhysicsSystem::builder()
+        .max_bodies(16)
+        .layers(FirstLayerOnly, layers.clone(), layers)
+        .build()
+        .unwrap();
+
+    let shape = box_shape(Vec3::ONE);
+    let body_interface = system.body_interface();
+    let body = unsafe {
+        body_interface.create_body(&JPC_BodyCreationSettings {
+            Position: RVec3::ZERO.into_jolt(),
+            Rotation: Quat::IDENTITY.into_jolt(),
+            MotionType: JPC_MOTION_TYPE_STATIC,
+            ObjectLayer: second.raw(),
+            Shape: shape.raw(),
+            ..Default::default()
+        })
+    }
+    .unwrap();
+
+    body_interface.add_body(body.id(), JPC_ACTIVATION_DONT_ACTIVATE);
+}
+
+struct PanickingBodyFilter;
+
+impl BodyFilter for PanickingBodyFilter {
+    fn should_collide(&self, _body_id: BodyId) -> bool {
+        panic!("body filter is broken");
+    }
+
+    fn should_collide_locked(&self, _body: &mut Body) -> bool {
+        true
+    }
+}
+
+#[test]
+fn query_panics_are_resumed_on_their_thread() {
+    let world = World::new();
+    world.add_static(&box_shape(Vec3::ONE), RVec3::ZERO);
+
+    let system = &world.system;
+    let ray = || RayCastArgs {
+        ray: RRayCast {
+            origin: RVec3::new(0.0, 5.0, 0.0),
+            direction: Vec3::new(0.0, -10.0, 0.0),
+        },
+        ..Default::default()
+    };
+
+    let result = thread::scope(|scope| {
+        scope
+            .spawn(|| {
+                system.narrow_phase_query().cast_ray(RayCastArgs {
+                    body_filter: Some(BodyFilterImpl::new(PanickingBodyFilter)),
+                    ..ray()
+                })
+            })
+            .join()
+    });
+
+    let payload = result.unwrap_err();
+    assert_eq!(
+        payload.downcast_ref::<&str>(),
+        Some(&"body filter is broken")
+    );
+
+    // Nothing was left behind for this thread to resume.
+    assert!(system.narrow_phase_query().cast_ray(ray()).is_some());
+}
@@ -0,0 +1,134 @@
+mod framework;
+
+use rolt::{
+    Color, ConvexHullShapeSettings, IndexedTriangle, MeshShapeSettings, PhysicsMaterial,
+    PhysicsMaterialList, RRayCast, RVec3, RayCastArgs, TaperedCapsuleShapeSettings, Vec3,
+};
+
+use crate::framework::*;
+
+#[test]
+fn cast_hit_finds_material_data() {
+    let world = World::new();
+
+    let mut materials = PhysicsMaterialList::new();
+    let grass = materials.push(PhysicsMaterial::new("grass", Color::new(0, 255, 0, 255), 1));
+    let stone = materials.push(PhysicsMaterial::new(
+        "stone",
+        Color::new(128, 128, 128, 255),
+        2,
+    ));
+
+    // Two quads facing up, grass on negative X and stone on positive X.
+    let vertices = vec![
+        Vec3::new(-2.0, 0.0, -1.0),
+        Vec3::new(-2.0, 0.0, 1.0),
+        Vec3::new(0.0, 0.0, -1.0),
+        Vec3::new(0.0, 0.0, 1.0),
+        Vec3::new(2.0, 0.0, -1.0),
+        Vec3::new(2.0, 0.0, 1.0),
+    ];
+    let triangles = vec![
+        IndexedTriangle::new([0, 1, 2], grass),
+        IndexedTriangle::new([2, 1, 3], grass),
+        IndexedTriangle::new([2, 3, 4], stone),
+        IndexedTriangle::new([4, 3, 5], stone),
+    ];
+
+    let mut settings = MeshShapeSettings::new(vertices, triangles);
+    settings.materials = materials.raw_materials();
+    let shape = settings.create().unwrap();
+    let body_id = world.add_static(&shape, RVec3::ZERO);
+
+    let narrow_phase = world.system.narrow_phase_query();
+    let body_interface = world.system.body_interface();
+
+    for (x, expected) in [(-1.0, 1), (1.0, 2)] {
+        let hit = narrow_phase
+            .cast_ray(RayCastArgs {
+                ray: RRayCast {
+                    origin: RVec3::new(x, 5.0, 0.0),
+                    direction: Vec3::new(0.0, -10.0, 0.0),
+                },
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(hit.body_id, body_id);
+
+        let material = body_interface
+            .material(hit.body_id, hit.sub_shape_id)
+            .unwrap();
+        assert_eq!(*materials.find(&material).unwrap().data(), expected);
+        assert!(shape.material(hit.sub_shape_id) == Some(material));
+
+        let found = materials.find_for_shape(&shape, hit.sub_shape_id).unwrap();
+        assert_eq!(*found.data(), expected);
+    }
+}
+
+#[test]
+fn default_material_is_not_in_list() {
+    let world = World::new();
+    let materials = PhysicsMaterialList::<u32>::new();
+    let shape = box_shape(Vec3::ONE);
+    let body_id = world.add_static(&shape, RVec3::ZERO);
+
+    let sub_shape_id = Default::default();
+    let material = world
+        .system
+        .body_interface()
+        .material(body_id, sub_shape_id);
+
+    // Jolt falls back to its default material, which isn't one of ours.
+    let material = material.unwrap();
+    assert!(materials.find(&material).is_none());
+    assert!(shape.material(sub_shape_id) == Some(material));
+}
+
+#[test]
+fn convex_shapes_use_their_material() {
+    let world = World::new();
+
+    let mut materials = PhysicsMaterialList::new();
+    let ice = materials.push(PhysicsMaterial::new(
+        "ice",
+        Color::new(200, 200, 255, 255),
+        1,
+    ));
+    let wood = materials.push(PhysicsMaterial::new(
+        "wood",
+        Color::new(150, 100, 50, 255),
+        2,
+    ));
+
+    let corners = (0..8)
+        .map(|i| Vec3::new((i & 1) as f32, (i >> 1 & 1) as f32, (i >> 2) as f32) - 0.5)
+        .collect();
+    let mut hull = ConvexHullShapeSettings::new(corners);
+    hull.material = Some(materials.get(ice).unwrap().to_ref());
+    world.add_static(&hull.create().unwrap(), RVec3::new(-2.0, 0.0, 0.0));
+
+    let mut capsule = TaperedCapsuleShapeSettings::new(0.5, 0.25, 0.5);
+    capsule.material = Some(materials.get(wood).unwrap().to_ref());
+    world.add_static(&capsule.create().unwrap(), RVec3::new(2.0, 0.0, 0.0));
+
+    let narrow_phase = world.system.narrow_phase_query();
+    let body_interface = world.system.body_interface();
+
+    for (x, expected) in [(-2.0, 1), (2.0, 2)] {
+        let hit = narrow_phase
+            .cast_ray(RayCastArgs {
+                ray: RRayCast {
+                    origin: RVec3::new(x, 5.0, 0.0),
+                    direction: Vec3::new(0.0, -10.0, 0.0),
+                },
+                ..Default::default()
+            })
+            .unwrap();
+
+        let material = body_interface
+            .material(hit.body_id, hit.sub_shape_id)
+            .unwrap();
+        assert_eq!(*materials.find(&material).unwrap().data(), expected);
+    }
+}
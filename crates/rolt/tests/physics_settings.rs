@@ -0,0 +1,82 @@
+mod framework;
+
+use rolt::{FromJolt, IntoJolt, PhysicsSettings, PhysicsSystem, Vec3};
+
+use crate::framework::*;
+
+/// Settings with every field changed from Jolt's defaults.
+fn custom_settings() -> PhysicsSettings {
+    let defaults = PhysicsSettings::default();
+
+    PhysicsSettings {
+        num_velocity_steps: defaults.num_velocity_steps + 2,
+        num_position_steps: defaults.num_position_steps + 1,
+        baumgarte: 0.5,
+        speculative_contact_distance: 0.05,
+        penetration_slop: 0.01,
+        linear_cast_threshold: 0.5,
+        linear_cast_max_penetration: 0.1,
+        min_velocity_for_restitution: 2.0,
+        allow_sleeping: !defaults.allow_sleeping,
+        time_before_sleep: 1.0,
+        point_velocity_sleep_threshold: 0.1,
+        deterministic_simulation: !defaults.deterministic_simulation,
+        constraint_warm_start: !defaults.constraint_warm_start,
+        use_body_pair_contact_cache: !defaults.use_body_pair_contact_cache,
+        use_manifold_reduction: !defaults.use_manifold_reduction,
+        use_large_island_splitter: !defaults.use_large_island_splitter,
+        check_active_edges: !defaults.check_active_edges,
+    }
+}
+
+#[test]
+fn settings_round_trip() {
+    let settings = custom_settings();
+    assert_ne!(settings, PhysicsSettings::default());
+
+    let jolt = settings.into_jolt();
+    assert_eq!(jolt.NumVelocitySteps, settings.num_velocity_steps);
+    assert_eq!(jolt.Baumgarte, settings.baumgarte);
+    assert_eq!(jolt.CheckActiveEdges, settings.check_active_edges);
+
+    assert_eq!(PhysicsSettings::from_jolt(jolt), settings);
+}
+
+#[test]
+fn builder_applies_gravity_and_settings() {
+    global_init();
+
+    let layers = collision_layers();
+    let gravity = Vec3::new(0.0, -3.7, 0.0);
+    let settings = custom_settings();
+
+    let system = PhysicsSystem::builder()
+        .max_bodies(16)
+        .collision_layers(&layers)
+        .gravity(gravity)
+        .physics_settings(settings)
+        .build()
+        .unwrap();
+
+    assert_eq!(system.gravity(), gravity);
+    assert_eq!(system.physics_settings(), settings);
+}
+
+#[test]
+fn set_gravity_and_settings() {
+    let mut world = World::new();
+    let default_gravity = world.system.gravity();
+    assert_eq!(world.system.physics_settings(), PhysicsSettings::default());
+
+    world.system.set_gravity(Vec3::ZERO);
+    assert_eq!(world.system.gravity(), Vec3::ZERO);
+    assert_ne!(default_gravity, Vec3::ZERO);
+
+    world.system.set_physics_settings(custom_settings());
+    assert_eq!(world.system.physics_settings(), custom_settings());
+
+    // Settings still apply after a step.
+    world.step();
+    assert_eq!(world.system.physics_settings(), custom_settings());
+    assert_eq!(world.system.gravity(), Vec3::ZERO);
+}
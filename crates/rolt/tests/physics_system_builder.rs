@@ -0,0 +1,69 @@
+use rolt::{
+    BroadPhaseLayerInterfaceImpl, CollisionLayers, ObjectLayerPairFilterImpl,
+    ObjectVsBroadPhaseLayerFilterImpl, PhysicsSystem, PhysicsSystemCapacities, PhysicsSystemError,
+};
+
+#[test]
+fn build_requires_layers() {
+    let result = PhysicsSystem::builder().build();
+
+    assert_eq!(result.err(), Some(PhysicsSystemError::MissingLayers));
+}
+
+#[test]
+fn build_rejects_invalid_capacities() {
+    let result = PhysicsSystem::builder().max_bodies(0).build();
+    assert_eq!(result.err(), Some(PhysicsSystemError::InvalidMaxBodies(0)));
+
+    let result = PhysicsSystem::builder()
+        .max_bodies(PhysicsSystemCapacities::MAX_BODIES_LIMIT + 1)
+        .build();
+    assert!(matches!(
+        result.err(),
+        Some(PhysicsSystemError::InvalidMaxBodies(_))
+    ));
+
+    let result = PhysicsSystem::builder().num_body_mutexes(3).build();
+    assert_eq!(
+        result.err(),
+        Some(PhysicsSystemError::InvalidNumBodyMutexes(3))
+    );
+
+    let result = PhysicsSystem::builder()
+        .capacities(PhysicsSystemCapacities {
+            max_contact_constraints: 0,
+            ..Default::default()
+        })
+        .build();
+    assert_eq!(
+        result.err(),
+        Some(PhysicsSystemError::ZeroCapacity("max_contact_constraints"))
+    );
+}
+
+#[test]
+fn build_accepts_borrowed_layers() {
+    rolt::register_default_allocator();
+
+    let mut layers = CollisionLayers::builder()
+        .broad_phase_layer("default")
+        .object_layer("default", "default")
+        .collide("default", "default")
+        .build()
+        .unwrap();
+    let mut object_vs_broad_phase = layers.clone();
+    let mut object_pairs = layers.clone();
+
+    let system = PhysicsSystem::builder()
+        .layers(
+            BroadPhaseLayerInterfaceImpl::new_borrowed(&mut layers),
+            ObjectVsBroadPhaseLayerFilterImpl::new_borrowed(&mut object_vs_broad_phase),
+            ObjectLayerPairFilterImpl::new_borrowed(&mut object_pairs),
+        )
+        .build()
+        .unwrap();
+
+    // The layers can be used again once the system is gone.
+    drop(system);
+    assert!(layers.object_layer("default").is_some());
+}
@@ -0,0 +1,49 @@
+use joltc_sys::*;
+use rolt::PhysicsUpdateError;
+
+#[test]
+fn bits_match_jolt() {
+    assert_eq!(
+        PhysicsUpdateError::MANIFOLD_CACHE_FULL.bits(),
+        JPC_PHYSICS_UPDATE_ERROR_MANIFOLD_CACHE_FULL
+    );
+    assert_eq!(
+        PhysicsUpdateError::BODY_PAIR_CACHE_FULL.bits(),
+        JPC_PHYSICS_UPDATE_ERROR_BODY_PAIR_CACHE_FULL
+    );
+    assert_eq!(
+        PhysicsUpdateError::CONTACT_CONSTRAINTS_FULL.bits(),
+        JPC_PHYSICS_UPDATE_ERROR_CONTACT_CONSTRAINTS_FULL
+    );
+
+    let unknown = PhysicsUpdateError::from_bits_retain(1 << 31);
+    assert_eq!(unknown.bits(), 1 << 31);
+    assert!(!unknown.intersects(PhysicsUpdateError::all()));
+}
+
+#[test]
+fn display_lists_every_full_buffer() {
+    assert_eq!(
+        PhysicsUpdateError::BODY_PAIR_CACHE_FULL.to_string(),
+        "physics update dropped contacts: body pair cache is full"
+    );
+
+    assert_eq!(
+        (PhysicsUpdateError::MANIFOLD_CACHE_FULL | PhysicsUpdateError::CONTACT_CONSTRAINTS_FULL)
+            .to_string(),
+        "physics update dropped contacts: manifold cache is full, contact constraints buffer is full"
+    );
+
+    assert_eq!(
+        PhysicsUpdateError::from_bits_retain(0x80).to_string(),
+        "physics update failed with error 0x80"
+    );
+}
+
+#[test]
+fn is_an_error() {
+    let err: Box<dyn std::error::Error> = Box::new(PhysicsUpdateError::all());
+
+    assert!(err.source().is_none());
+    assert!(err.to_string().contains("body pair cache is full"));
+}
@@ -0,0 +1,87 @@
+use rolt::{
+    EmptyShapeSettings, PlaneShapeSettings, ShapeError, TaperedCapsuleShapeSettings,
+    TaperedCylinderShapeSettings, Vec3,
+};
+
+#[test]
+fn tapered_capsule_rejects_embedded_sphere() {
+    let mut settings = TaperedCapsuleShapeSettings {
+        user_data: 0,
+        material: None,
+        density: 1000.0,
+        half_height_of_tapered_cylinder: 1.0,
+        top_radius: 0.5,
+        bottom_radius: 1.0,
+    };
+    assert_eq!(settings.validate(), Ok(()));
+
+    settings.bottom_radius = 3.0;
+    assert!(matches!(settings.validate(), Err(ShapeError::Invalid(_))));
+
+    settings.bottom_radius = f32::NAN;
+    assert!(matches!(settings.validate(), Err(ShapeError::Invalid(_))));
+}
+
+#[test]
+fn tapered_cylinder_limits_convex_radius() {
+    let mut settings = TaperedCylinderShapeSettings {
+        user_data: 0,
+        material: None,
+        density: 1000.0,
+        half_height: 1.0,
+        top_radius: 0.0,
+        bottom_radius: 1.0,
+        convex_radius: 0.0,
+    };
+    assert_eq!(settings.validate(), Ok(()));
+
+    settings.convex_radius = 0.05;
+    assert!(matches!(settings.validate(), Err(ShapeError::Invalid(_))));
+
+    settings.top_radius = 0.5;
+    assert_eq!(settings.validate(), Ok(()));
+}
+
+#[test]
+fn plane_and_empty_validation() {
+    let mut plane = PlaneShapeSettings {
+        user_data: 0,
+        material: None,
+        normal: Vec3::Y,
+        constant: 0.0,
+        half_extent: 1000.0,
+    };
+    assert_eq!(plane.validate(), Ok(()));
+
+    plane.normal = Vec3::new(0.0, 2.0, 0.0);
+    assert!(matches!(plane.validate(), Err(ShapeError::Invalid(_))));
+
+    assert_eq!(EmptyShapeSettings::default().validate(), Ok(()));
+    assert!(EmptyShapeSettings::new(Vec3::splat(f32::INFINITY))
+        .validate()
+        .is_err());
+}
+
+#[test]
+fn defaults_match_jolt() {
+    let capsule = TaperedCapsuleShapeSettings::default();
+    assert_eq!(capsule.user_data, 0);
+    assert_eq!(
+        capsule.density,
+        TaperedCapsuleShapeSettings::new(1.0, 0.5, 0.25).density
+    );
+
+    let cylinder = TaperedCylinderShapeSettings::default();
+    assert_eq!(cylinder.user_data, 0);
+    assert_eq!(
+        cylinder.density,
+        TaperedCylinderShapeSettings::new(1.0, 0.5, 0.25).density
+    );
+
+    let plane = PlaneShapeSettings::default();
+    assert_eq!(plane.user_data, 0);
+    assert_eq!(
+        plane.half_extent,
+        PlaneShapeSettings::new(Vec3::Y, 0.0).half_extent
+    );
+}
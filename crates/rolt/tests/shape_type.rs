@@ -0,0 +1,38 @@
+use joltc_sys::*;
+use rolt::{FromJolt, ShapeSubType, ShapeType};
+
+#[test]
+fn known_shape_types() {
+    assert_eq!(
+        ShapeType::from_jolt(JPC_SHAPE_TYPE_COMPOUND),
+        ShapeType::Compound
+    );
+    assert_eq!(ShapeType::from_jolt(JPC_SHAPE_TYPE_USER4), ShapeType::User4);
+    assert_eq!(
+        ShapeSubType::from_jolt(JPC_SHAPE_SUB_TYPE_TAPERED_CYLINDER),
+        ShapeSubType::TaperedCylinder
+    );
+    assert_eq!(
+        ShapeSubType::from_jolt(JPC_SHAPE_SUB_TYPE_USER1),
+        ShapeSubType::User(1)
+    );
+    assert_eq!(
+        ShapeSubType::from_jolt(JPC_SHAPE_SUB_TYPE_USER_CONVEX8),
+        ShapeSubType::UserConvex(8)
+    );
+}
+
+#[test]
+fn unknown_shape_types_are_kept() {
+    let shape_type: JPC_ShapeType = 200;
+    assert_eq!(
+        ShapeType::from_jolt(shape_type),
+        ShapeType::Unknown(shape_type)
+    );
+
+    let sub_type: JPC_ShapeSubType = 200;
+    assert_eq!(
+        ShapeSubType::from_jolt(sub_type),
+        ShapeSubType::Unknown(sub_type)
+    );
+}
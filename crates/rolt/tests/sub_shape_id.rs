@@ -0,0 +1,38 @@
+use rolt::{SubShapeId, SubShapeIdCreator};
+
+#[test]
+fn empty_by_default() {
+    assert!(SubShapeId::default().is_empty());
+    assert!(SubShapeIdCreator::new().id().is_empty());
+}
+
+#[test]
+fn push_pop_round_trip() {
+    // A compound with 5 children (3 bits) containing a compound with 2 children
+    // (1 bit) containing a mesh that uses 20 bits.
+    let creator = SubShapeIdCreator::new()
+        .push_id(4, 3)
+        .push_id(1, 1)
+        .push_id(0xABCDE, 20);
+
+    assert_eq!(creator.num_bits_written(), 24);
+
+    let id = creator.id();
+    let (outer, id) = id.pop_id(3);
+    let (inner, id) = id.pop_id(1);
+    let (triangle, id) = id.pop_id(20);
+
+    assert_eq!(outer, 4);
+    assert_eq!(inner, 1);
+    assert_eq!(triangle, 0xABCDE);
+    assert!(id.is_empty());
+}
+
+#[test]
+fn push_pop_all_bits() {
+    let id = SubShapeIdCreator::new().push_id(0x1234_5678, 32).id();
+    let (value, remainder) = id.pop_id(32);
+
+    assert_eq!(value, 0x1234_5678);
+    assert!(remainder.is_empty());
+}
@@ -0,0 +1,27 @@
+use rolt::{
+    BodyInterface, BroadPhaseLayerInterfaceImpl, CollisionLayers, ContactListenerImpl,
+    GroupFilterImpl, GroupFilterTable, NarrowPhaseQuery, ObjectLayerPairFilterImpl,
+    ObjectVsBroadPhaseLayerFilterImpl, PhysicsSystem, SimShapeFilterImpl,
+};
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn physics_system_can_be_shared() {
+    assert_send_sync::<PhysicsSystem>();
+    assert_send_sync::<BodyInterface<'_>>();
+    assert_send_sync::<NarrowPhaseQuery<'_>>();
+}
+
+#[test]
+fn thread_safe_callbacks_can_be_shared() {
+    assert_send_sync::<ContactListenerImpl<'_>>();
+    assert_send_sync::<GroupFilterImpl<'_>>();
+    assert_send_sync::<BroadPhaseLayerInterfaceImpl<'_>>();
+    assert_send_sync::<ObjectVsBroadPhaseLayerFilterImpl<'_>>();
+    assert_send_sync::<ObjectLayerPairFilterImpl<'_>>();
+    assert_send_sync::<SimShapeFilterImpl<'_>>();
+
+    assert_send_sync::<CollisionLayers>();
+    assert_send_sync::<GroupFilterTable>();
+}